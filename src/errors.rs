@@ -1,33 +1,47 @@
 use block_modes::BlockModeError;
+use colored::Colorize;
 use hex::FromHexError;
 use nix::errno::Errno;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections,
+    array::TryFromSliceError,
+    char::CharTryFromError,
+    collections::{self, HashMap, VecDeque},
     convert::Infallible,
-    fmt, io, net,
-    num::{ParseIntError, TryFromIntError},
+    ffi::OsString,
+    fmt,
+    io::{self, IsTerminal},
+    net,
+    num::{ParseFloatError, ParseIntError, TryFromIntError},
     path,
-    str::Utf8Error,
+    process::ExitCode,
+    str::{FromStr, Utf8Error},
     string::FromUtf8Error,
-    sync::{self, Arc, RwLock},
+    sync::{self, Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
     thread, time,
 };
 
-#[cfg(rust_comp_feature = "try_trait_v2")]
-use std::{convert::Infallible, ops::FromResidual};
+#[cfg(feature = "nightly-try")]
+use std::ops::{ControlFlow, FromResidual, Try};
 
 // Imported for conversion to new items
 #[allow(deprecated)]
 use crate::errors_dep::SystemError;
-use crate::{log, log::LogLevel, stringy::Stringy};
+use crate::{functions::current_timestamp, log, log::LogLevel, stringy::Stringy};
 // #[allow(deprecated)]
 // use logging::errors::LoggerError;
 // #[allow(deprecated)]
 // use recs::errors::RecsError;
 
+pub mod stats;
+
+lazy_static::lazy_static! {
+    static ref DISPLAY_STYLE: RwLock<DisplayStyle> = RwLock::new(DisplayStyle::Plain);
+    static ref GLOBAL_ERRORS: ErrorArray = ErrorArray::new_container();
+}
+
 /// Represents different types of generic errors.
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, PartialOrd, Ord, Hash)]
 pub enum Errors {
     // File-related errors
     /// Error encountered while opening a file.
@@ -100,6 +114,8 @@ pub enum Errors {
     Unauthorized,
     /// Resource not found.
     NotFound,
+    /// Resource already exists.
+    AlreadyExists,
 
     // Network and protocol errors
     /// Network error.
@@ -196,13 +212,550 @@ pub enum Errors {
     DEPRECS,
 }
 
+// Parses a variant name ignoring case, for kinds listed in config files or flags
+impl FromStr for Errors {
+    type Err = ErrorArrayItem;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Errors::ALL
+            .iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| {
+                ErrorArrayItem::new(
+                    Errors::InvalidType,
+                    format!("'{}' is not a known error kind", s),
+                )
+            })
+    }
+}
+
+/// Broad area an [`Errors`] kind belongs to, see [`Errors::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCategory {
+    /// Files, directories and their permissions.
+    Filesystem,
+    /// Connections, protocols and remote endpoints.
+    Network,
+    /// Permissions, authentication and identities.
+    Auth,
+    /// Malformed or invalid data and encodings.
+    Data,
+    /// Application state and configuration.
+    Config,
+    /// Memory and other resource limits.
+    Resource,
+    /// Locks and synchronization.
+    Sync,
+    /// Supervised child processes.
+    Process,
+    /// Everything else.
+    General,
+}
+
+impl Errors {
+    /// Every kind, in declaration order.
+    pub const ALL: &'static [Errors] = &[
+        Errors::OpeningFile,
+        Errors::ReadingFile,
+        Errors::CreatingFile,
+        Errors::DeletingFile,
+        Errors::SettingPermissionsFile,
+        Errors::UntaringFile,
+        Errors::InvalidFile,
+        Errors::CreatingDirectory,
+        Errors::DeletingDirectory,
+        Errors::SettingPermissionsDirectory,
+        Errors::JsonCreation,
+        Errors::JsonReading,
+        Errors::InvalidType,
+        Errors::InvalidChunkData,
+        Errors::InvalidHMACData,
+        Errors::InvalidHMACSize,
+        Errors::InvalidKey,
+        Errors::InvalidHexData,
+        Errors::InvalidIvData,
+        Errors::InvalidBlockData,
+        Errors::InvalidAuthRequest,
+        Errors::InvalidMapRequest,
+        Errors::InvalidMapVersion,
+        Errors::InvalidMapData,
+        Errors::InvalidMapHash,
+        Errors::InvalidBufferFit,
+        Errors::InvalidUtf8Data,
+        Errors::InvalidSignature,
+        Errors::PermissionDenied,
+        Errors::Unauthorized,
+        Errors::NotFound,
+        Errors::AlreadyExists,
+        Errors::Network,
+        Errors::Protocol,
+        Errors::ConnectionError,
+        Errors::Timeout,
+        Errors::ConnectionTimedOut,
+        Errors::PortalNotFound,
+        Errors::PortalConnectionFailed,
+        Errors::AuthenticationError,
+        Errors::IdentityError,
+        Errors::IdentityInvalid,
+        Errors::AppState,
+        Errors::ConfigReading,
+        Errors::ConfigParsing,
+        Errors::OutOfMemory,
+        Errors::OverRamLimit,
+        Errors::MessageDecode,
+        Errors::MessageEncode,
+        Errors::TimedOut,
+        Errors::LockWithTimeoutRead,
+        Errors::LockWithTimeoutWrite,
+        Errors::SupervisedChild,
+        Errors::SupervisedChildDied,
+        Errors::SupervisedChildKilled,
+        Errors::SupervisedChildLost,
+        Errors::SupervisedChildFat,
+        Errors::InputOutput,
+        Errors::GeneralError,
+        Errors::InitializationError,
+        Errors::SecretArray,
+        Errors::Git,
+        Errors::GitFileMissing,
+        Errors::GitFileIllegible,
+        Errors::ToggleControl,
+        Errors::DEPSYSTEM,
+        Errors::DEPLOGGER,
+        Errors::DEPRECS,
+    ];
+
+    /// Returns the variant name, which [`Errors::from_str`](std::str::FromStr) accepts back.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Errors::OpeningFile => "OpeningFile",
+            Errors::ReadingFile => "ReadingFile",
+            Errors::CreatingFile => "CreatingFile",
+            Errors::DeletingFile => "DeletingFile",
+            Errors::SettingPermissionsFile => "SettingPermissionsFile",
+            Errors::UntaringFile => "UntaringFile",
+            Errors::InvalidFile => "InvalidFile",
+            Errors::CreatingDirectory => "CreatingDirectory",
+            Errors::DeletingDirectory => "DeletingDirectory",
+            Errors::SettingPermissionsDirectory => "SettingPermissionsDirectory",
+            Errors::JsonCreation => "JsonCreation",
+            Errors::JsonReading => "JsonReading",
+            Errors::InvalidType => "InvalidType",
+            Errors::InvalidChunkData => "InvalidChunkData",
+            Errors::InvalidHMACData => "InvalidHMACData",
+            Errors::InvalidHMACSize => "InvalidHMACSize",
+            Errors::InvalidKey => "InvalidKey",
+            Errors::InvalidHexData => "InvalidHexData",
+            Errors::InvalidIvData => "InvalidIvData",
+            Errors::InvalidBlockData => "InvalidBlockData",
+            Errors::InvalidAuthRequest => "InvalidAuthRequest",
+            Errors::InvalidMapRequest => "InvalidMapRequest",
+            Errors::InvalidMapVersion => "InvalidMapVersion",
+            Errors::InvalidMapData => "InvalidMapData",
+            Errors::InvalidMapHash => "InvalidMapHash",
+            Errors::InvalidBufferFit => "InvalidBufferFit",
+            Errors::InvalidUtf8Data => "InvalidUtf8Data",
+            Errors::InvalidSignature => "InvalidSignature",
+            Errors::PermissionDenied => "PermissionDenied",
+            Errors::Unauthorized => "Unauthorized",
+            Errors::NotFound => "NotFound",
+            Errors::AlreadyExists => "AlreadyExists",
+            Errors::Network => "Network",
+            Errors::Protocol => "Protocol",
+            Errors::ConnectionError => "ConnectionError",
+            Errors::Timeout => "Timeout",
+            Errors::ConnectionTimedOut => "ConnectionTimedOut",
+            Errors::PortalNotFound => "PortalNotFound",
+            Errors::PortalConnectionFailed => "PortalConnectionFailed",
+            Errors::AuthenticationError => "AuthenticationError",
+            Errors::IdentityError => "IdentityError",
+            Errors::IdentityInvalid => "IdentityInvalid",
+            Errors::AppState => "AppState",
+            Errors::ConfigReading => "ConfigReading",
+            Errors::ConfigParsing => "ConfigParsing",
+            Errors::OutOfMemory => "OutOfMemory",
+            Errors::OverRamLimit => "OverRamLimit",
+            Errors::MessageDecode => "MessageDecode",
+            Errors::MessageEncode => "MessageEncode",
+            Errors::TimedOut => "TimedOut",
+            Errors::LockWithTimeoutRead => "LockWithTimeoutRead",
+            Errors::LockWithTimeoutWrite => "LockWithTimeoutWrite",
+            Errors::SupervisedChild => "SupervisedChild",
+            Errors::SupervisedChildDied => "SupervisedChildDied",
+            Errors::SupervisedChildKilled => "SupervisedChildKilled",
+            Errors::SupervisedChildLost => "SupervisedChildLost",
+            Errors::SupervisedChildFat => "SupervisedChildFat",
+            Errors::InputOutput => "InputOutput",
+            Errors::GeneralError => "GeneralError",
+            Errors::InitializationError => "InitializationError",
+            Errors::SecretArray => "SecretArray",
+            Errors::Git => "Git",
+            Errors::GitFileMissing => "GitFileMissing",
+            Errors::GitFileIllegible => "GitFileIllegible",
+            Errors::ToggleControl => "ToggleControl",
+            Errors::DEPSYSTEM => "DEPSYSTEM",
+            Errors::DEPLOGGER => "DEPLOGGER",
+            Errors::DEPRECS => "DEPRECS",
+        }
+    }
+
+    /// Returns the broad area this kind of error belongs to.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Errors::OpeningFile
+            | Errors::ReadingFile
+            | Errors::CreatingFile
+            | Errors::DeletingFile
+            | Errors::SettingPermissionsFile
+            | Errors::UntaringFile
+            | Errors::InvalidFile
+            | Errors::CreatingDirectory
+            | Errors::DeletingDirectory
+            | Errors::SettingPermissionsDirectory
+            | Errors::NotFound
+            | Errors::AlreadyExists => ErrorCategory::Filesystem,
+            Errors::JsonCreation
+            | Errors::JsonReading
+            | Errors::InvalidType
+            | Errors::InvalidChunkData
+            | Errors::InvalidHMACData
+            | Errors::InvalidHMACSize
+            | Errors::InvalidKey
+            | Errors::InvalidHexData
+            | Errors::InvalidIvData
+            | Errors::InvalidBlockData
+            | Errors::InvalidAuthRequest
+            | Errors::InvalidMapRequest
+            | Errors::InvalidMapVersion
+            | Errors::InvalidMapData
+            | Errors::InvalidMapHash
+            | Errors::InvalidBufferFit
+            | Errors::InvalidUtf8Data
+            | Errors::InvalidSignature
+            | Errors::MessageDecode
+            | Errors::MessageEncode => ErrorCategory::Data,
+            Errors::PermissionDenied
+            | Errors::Unauthorized
+            | Errors::AuthenticationError
+            | Errors::IdentityError
+            | Errors::IdentityInvalid => ErrorCategory::Auth,
+            Errors::Network
+            | Errors::Protocol
+            | Errors::ConnectionError
+            | Errors::Timeout
+            | Errors::ConnectionTimedOut
+            | Errors::PortalNotFound
+            | Errors::PortalConnectionFailed => ErrorCategory::Network,
+            Errors::AppState | Errors::ConfigReading | Errors::ConfigParsing => {
+                ErrorCategory::Config
+            }
+            Errors::OutOfMemory | Errors::OverRamLimit => ErrorCategory::Resource,
+            Errors::TimedOut | Errors::LockWithTimeoutRead | Errors::LockWithTimeoutWrite => {
+                ErrorCategory::Sync
+            }
+            Errors::SupervisedChild
+            | Errors::SupervisedChildDied
+            | Errors::SupervisedChildKilled
+            | Errors::SupervisedChildLost
+            | Errors::SupervisedChildFat => ErrorCategory::Process,
+            Errors::InputOutput
+            | Errors::GeneralError
+            | Errors::InitializationError
+            | Errors::SecretArray
+            | Errors::Git
+            | Errors::GitFileMissing
+            | Errors::GitFileIllegible
+            | Errors::ToggleControl
+            | Errors::DEPSYSTEM
+            | Errors::DEPLOGGER
+            | Errors::DEPRECS => ErrorCategory::General,
+        }
+    }
+
+    /// Returns the stable numeric code of this kind, for wire protocols and exit codes.
+    ///
+    /// Codes are assigned explicitly, grouped by hundreds per area, and never change
+    /// when variants are reordered. New variants take the next free code in their group.
+    pub fn code(&self) -> u16 {
+        match self {
+            Errors::OpeningFile => 101,
+            Errors::ReadingFile => 102,
+            Errors::CreatingFile => 103,
+            Errors::DeletingFile => 104,
+            Errors::SettingPermissionsFile => 105,
+            Errors::UntaringFile => 106,
+            Errors::InvalidFile => 107,
+            Errors::CreatingDirectory => 201,
+            Errors::DeletingDirectory => 202,
+            Errors::SettingPermissionsDirectory => 203,
+            Errors::JsonCreation => 301,
+            Errors::JsonReading => 302,
+            Errors::InvalidType => 401,
+            Errors::InvalidChunkData => 402,
+            Errors::InvalidHMACData => 403,
+            Errors::InvalidHMACSize => 404,
+            Errors::InvalidKey => 405,
+            Errors::InvalidHexData => 406,
+            Errors::InvalidIvData => 407,
+            Errors::InvalidBlockData => 408,
+            Errors::InvalidAuthRequest => 409,
+            Errors::InvalidMapRequest => 410,
+            Errors::InvalidMapVersion => 411,
+            Errors::InvalidMapData => 412,
+            Errors::InvalidMapHash => 413,
+            Errors::InvalidBufferFit => 414,
+            Errors::InvalidUtf8Data => 415,
+            Errors::InvalidSignature => 416,
+            Errors::PermissionDenied => 501,
+            Errors::Unauthorized => 502,
+            Errors::NotFound => 503,
+            Errors::AlreadyExists => 504,
+            Errors::Network => 601,
+            Errors::Protocol => 602,
+            Errors::ConnectionError => 603,
+            Errors::Timeout => 604,
+            Errors::ConnectionTimedOut => 605,
+            Errors::PortalNotFound => 606,
+            Errors::PortalConnectionFailed => 607,
+            Errors::AuthenticationError => 701,
+            Errors::IdentityError => 702,
+            Errors::IdentityInvalid => 703,
+            Errors::AppState => 801,
+            Errors::ConfigReading => 802,
+            Errors::ConfigParsing => 803,
+            Errors::OutOfMemory => 901,
+            Errors::OverRamLimit => 902,
+            Errors::MessageDecode => 1001,
+            Errors::MessageEncode => 1002,
+            Errors::TimedOut => 1101,
+            Errors::LockWithTimeoutRead => 1102,
+            Errors::LockWithTimeoutWrite => 1103,
+            Errors::SupervisedChild => 1201,
+            Errors::SupervisedChildDied => 1202,
+            Errors::SupervisedChildKilled => 1203,
+            Errors::SupervisedChildLost => 1204,
+            Errors::SupervisedChildFat => 1205,
+            Errors::InputOutput => 1301,
+            Errors::GeneralError => 1302,
+            Errors::InitializationError => 1303,
+            Errors::SecretArray => 1304,
+            Errors::Git => 1401,
+            Errors::GitFileMissing => 1402,
+            Errors::GitFileIllegible => 1403,
+            Errors::ToggleControl => 1501,
+            Errors::DEPSYSTEM => 9001,
+            Errors::DEPLOGGER => 9002,
+            Errors::DEPRECS => 9003,
+        }
+    }
+
+    /// Returns the kind with the given code, see [`Errors::code`].
+    pub fn from_code(code: u16) -> Option<Errors> {
+        match code {
+            101 => Some(Errors::OpeningFile),
+            102 => Some(Errors::ReadingFile),
+            103 => Some(Errors::CreatingFile),
+            104 => Some(Errors::DeletingFile),
+            105 => Some(Errors::SettingPermissionsFile),
+            106 => Some(Errors::UntaringFile),
+            107 => Some(Errors::InvalidFile),
+            201 => Some(Errors::CreatingDirectory),
+            202 => Some(Errors::DeletingDirectory),
+            203 => Some(Errors::SettingPermissionsDirectory),
+            301 => Some(Errors::JsonCreation),
+            302 => Some(Errors::JsonReading),
+            401 => Some(Errors::InvalidType),
+            402 => Some(Errors::InvalidChunkData),
+            403 => Some(Errors::InvalidHMACData),
+            404 => Some(Errors::InvalidHMACSize),
+            405 => Some(Errors::InvalidKey),
+            406 => Some(Errors::InvalidHexData),
+            407 => Some(Errors::InvalidIvData),
+            408 => Some(Errors::InvalidBlockData),
+            409 => Some(Errors::InvalidAuthRequest),
+            410 => Some(Errors::InvalidMapRequest),
+            411 => Some(Errors::InvalidMapVersion),
+            412 => Some(Errors::InvalidMapData),
+            413 => Some(Errors::InvalidMapHash),
+            414 => Some(Errors::InvalidBufferFit),
+            415 => Some(Errors::InvalidUtf8Data),
+            416 => Some(Errors::InvalidSignature),
+            501 => Some(Errors::PermissionDenied),
+            502 => Some(Errors::Unauthorized),
+            503 => Some(Errors::NotFound),
+            504 => Some(Errors::AlreadyExists),
+            601 => Some(Errors::Network),
+            602 => Some(Errors::Protocol),
+            603 => Some(Errors::ConnectionError),
+            604 => Some(Errors::Timeout),
+            605 => Some(Errors::ConnectionTimedOut),
+            606 => Some(Errors::PortalNotFound),
+            607 => Some(Errors::PortalConnectionFailed),
+            701 => Some(Errors::AuthenticationError),
+            702 => Some(Errors::IdentityError),
+            703 => Some(Errors::IdentityInvalid),
+            801 => Some(Errors::AppState),
+            802 => Some(Errors::ConfigReading),
+            803 => Some(Errors::ConfigParsing),
+            901 => Some(Errors::OutOfMemory),
+            902 => Some(Errors::OverRamLimit),
+            1001 => Some(Errors::MessageDecode),
+            1002 => Some(Errors::MessageEncode),
+            1101 => Some(Errors::TimedOut),
+            1102 => Some(Errors::LockWithTimeoutRead),
+            1103 => Some(Errors::LockWithTimeoutWrite),
+            1201 => Some(Errors::SupervisedChild),
+            1202 => Some(Errors::SupervisedChildDied),
+            1203 => Some(Errors::SupervisedChildKilled),
+            1204 => Some(Errors::SupervisedChildLost),
+            1205 => Some(Errors::SupervisedChildFat),
+            1301 => Some(Errors::InputOutput),
+            1302 => Some(Errors::GeneralError),
+            1303 => Some(Errors::InitializationError),
+            1304 => Some(Errors::SecretArray),
+            1401 => Some(Errors::Git),
+            1402 => Some(Errors::GitFileMissing),
+            1403 => Some(Errors::GitFileIllegible),
+            1501 => Some(Errors::ToggleControl),
+            9001 => Some(Errors::DEPSYSTEM),
+            9002 => Some(Errors::DEPLOGGER),
+            9003 => Some(Errors::DEPRECS),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for transient errors where retrying the same operation may succeed:
+    /// timeouts, dropped connections and lock contention.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Errors::Network
+                | Errors::ConnectionError
+                | Errors::Timeout
+                | Errors::ConnectionTimedOut
+                | Errors::PortalConnectionFailed
+                | Errors::TimedOut
+                | Errors::LockWithTimeoutRead
+                | Errors::LockWithTimeoutWrite
+        )
+    }
+}
+
+/// How an [`ErrorArrayItem`] is rendered by `Display` and [`ErrorArrayItem::format_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayStyle {
+    /// `Kind: message [key=value, ...]`, suitable for log files.
+    #[default]
+    Plain,
+    /// Same layout as `Plain` with the kind highlighted. Color is only applied when
+    /// stderr is a terminal and `NO_COLOR` is unset.
+    Pretty,
+    /// `Kind: message` without any context pairs.
+    Short,
+}
+
+/// Sets the style used when displaying errors.
+pub fn set_display_style(style: DisplayStyle) {
+    let mut display_style = match DISPLAY_STYLE.write() {
+        Ok(style_guard) => style_guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *display_style = style;
+}
+
+/// Returns the style used when displaying errors.
+pub fn get_display_style() -> DisplayStyle {
+    match DISPLAY_STYLE.read() {
+        Ok(style_guard) => *style_guard,
+        Err(_) => DisplayStyle::Plain,
+    }
+}
+
+/// Returns the process-wide collector that [`ErrorArrayItem::report`] pushes into.
+///
+/// It is created on first use, needs no async runtime and can be shared between threads.
+/// The usual pattern is to report from anywhere and dump once at exit, either with
+/// [`drain_global`] or with `global().display()`, which logs and clears the collected
+/// errors the same way. [`ErrorArray::into_exit_code`] on a clone of it logs them and
+/// returns the exit code in one step.
+pub fn global() -> &'static ErrorArray {
+    &GLOBAL_ERRORS
+}
+
+/// Removes and returns every error reported into [`global`], oldest first.
+pub fn drain_global() -> Vec<ErrorArrayItem> {
+    GLOBAL_ERRORS.clone().drain()
+}
+
+fn color_enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none() && io::stderr().is_terminal()
+}
+
 /// Represents a generic error.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, PartialOrd, Ord, Eq)]
+///
+/// Errors compare by kind, message and context. `created_at` and the captured backtrace
+/// are ignored, so the same error built at two different times is still equal.
+#[derive(Debug, Clone, Deserialize)]
 pub struct ErrorArrayItem {
     /// Type of the error.
     pub err_type: Errors,
     /// Message associated with the error.
     pub err_mesg: Stringy,
+    /// Key-value pairs describing where the error happened (path, uid, operation, ...).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<(Stringy, Stringy)>,
+    /// Unix timestamp (seconds) of when the error was created, 0 if unknown.
+    /// Serialized as `timestamp`, `created_at` is still accepted when deserializing.
+    #[serde(default, rename = "timestamp", alias = "created_at")]
+    pub created_at: u64,
+    /// Where the error was created, only with the `backtrace` feature.
+    #[cfg(feature = "backtrace")]
+    #[serde(skip)]
+    backtrace: CapturedBacktrace,
+}
+
+/// Shares a captured backtrace between clones of an error.
+#[cfg(feature = "backtrace")]
+#[derive(Debug, Clone, Default)]
+struct CapturedBacktrace(Option<Arc<std::backtrace::Backtrace>>);
+
+#[cfg(feature = "backtrace")]
+impl CapturedBacktrace {
+    // Backtrace::capture is a no-op unless RUST_BACKTRACE or RUST_LIB_BACKTRACE enable it
+    fn capture() -> Self {
+        let backtrace = std::backtrace::Backtrace::capture();
+        match backtrace.status() {
+            std::backtrace::BacktraceStatus::Captured => Self(Some(Arc::new(backtrace))),
+            _ => Self(None),
+        }
+    }
+}
+
+impl PartialEq for ErrorArrayItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.err_type == other.err_type
+            && self.err_mesg == other.err_mesg
+            && self.context == other.context
+    }
+}
+
+impl Eq for ErrorArrayItem {}
+
+impl PartialOrd for ErrorArrayItem {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ErrorArrayItem {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (&self.err_type, &self.err_mesg, &self.context).cmp(&(
+            &other.err_type,
+            &other.err_mesg,
+            &other.context,
+        ))
+    }
 }
 
 impl ErrorArrayItem {
@@ -214,7 +767,288 @@ impl ErrorArrayItem {
         ErrorArrayItem {
             err_type: kind,
             err_mesg: Stringy::from(message),
+            context: Vec::new(),
+            created_at: current_timestamp(),
+            #[cfg(feature = "backtrace")]
+            backtrace: CapturedBacktrace::capture(),
+        }
+    }
+
+    /// Returns the backtrace captured when the error was created. `None` unless
+    /// `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` enabled capturing at the time.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.backtrace.0.as_deref()
+    }
+
+    /// Returns the Unix timestamp (seconds) of when the error was created.
+    pub fn timestamp(&self) -> u64 {
+        self.created_at
+    }
+
+    /// Builds an error from any `std::error::Error`, joining its `source()` chain
+    /// into the message, outermost first, separated by `": "`.
+    pub fn from_dyn(kind: Errors, err: &dyn std::error::Error) -> Self {
+        let mut message: String = err.to_string();
+        let mut source = err.source();
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        ErrorArrayItem::new(kind, message)
+    }
+
+    /// Owned form of [`ErrorArrayItem::from_dyn`], for use with `map_err`.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{ErrorArrayItem, Errors};
+    ///
+    /// let port = "80a"
+    ///     .parse::<u16>()
+    ///     .map_err(|e| ErrorArrayItem::from_err(Errors::ConfigParsing, e));
+    /// assert_eq!(port.unwrap_err().err_type, Errors::ConfigParsing);
+    /// ```
+    pub fn from_err<E: std::error::Error>(kind: Errors, err: E) -> Self {
+        ErrorArrayItem::from_dyn(kind, &err)
+    }
+
+    /// Builds an error of `kind` whose message is `message` formatted with `Display`.
+    pub fn from_display<D: fmt::Display>(kind: Errors, message: D) -> Self {
+        ErrorArrayItem::new(kind, message.to_string())
+    }
+
+    /// Returns the category of the error's kind, see [`Errors::category`].
+    pub fn category(&self) -> ErrorCategory {
+        self.err_type.category()
+    }
+
+    /// Returns `true` if the error's kind is transient, see [`Errors::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.err_type.is_retryable()
+    }
+
+    /// Returns `true` if both errors are of the same kind, whatever their messages.
+    pub fn same_kind(&self, other: &ErrorArrayItem) -> bool {
+        self.err_type == other.err_type
+    }
+
+    /// Pushes the error into the process-wide collector, see [`global`].
+    pub fn report(self) {
+        global().clone().push(self);
+    }
+
+    /// Formats the error with the given style instead of the configured one.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{DisplayStyle, ErrorArrayItem, Errors};
+    ///
+    /// let err = ErrorArrayItem::new(Errors::NotFound, "Missing").context("path", "/tmp/x");
+    /// assert_eq!(err.format_with(DisplayStyle::Plain), "NotFound: Missing [path=/tmp/x]");
+    /// assert_eq!(err.format_with(DisplayStyle::Short), "NotFound: Missing");
+    /// ```
+    pub fn format_with(&self, style: DisplayStyle) -> String {
+        self.format_styled(style, color_enabled())
+    }
+
+    // Pretty only colors the kind when `color` is set, so tests can reach that path
+    // without stderr being a terminal.
+    pub(crate) fn format_styled(&self, style: DisplayStyle, color: bool) -> String {
+        let kind: String = format!("{:?}", self.err_type);
+        let kind: String = match style {
+            DisplayStyle::Pretty if color => kind.bold().red().to_string(),
+            _ => kind,
+        };
+        let mut output: String = format!("{}: {}", kind, self.err_mesg);
+
+        if style != DisplayStyle::Short && !self.context.is_empty() {
+            let pairs: Vec<String> = self
+                .context
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            output.push_str(&format!(" [{}]", pairs.join(", ")));
+        }
+
+        output
+    }
+
+    /// Formats the error as a single `key=value` line for log shippers.
+    ///
+    /// The fields are always `kind`, `code`, `created_at` and `msg` in that order, followed
+    /// by each context pair as `ctx.<key>`. `msg` and context values are quoted with `\`,
+    /// `"` and line breaks escaped, so the line never contains a raw newline.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{ErrorArrayItem, Errors};
+    ///
+    /// let mut err = ErrorArrayItem::new(Errors::NotFound, "no \"app.conf\"").context("dir", "/etc");
+    /// err.created_at = 1700000000;
+    /// assert_eq!(
+    ///     err.to_log_line(),
+    ///     r#"kind=NotFound code=503 created_at=1700000000 msg="no \"app.conf\"" ctx.dir="/etc""#
+    /// );
+    /// ```
+    pub fn to_log_line(&self) -> String {
+        let mut line: String = format!(
+            "kind={:?} code={} created_at={} msg={}",
+            self.err_type,
+            self.err_type.code(),
+            self.created_at,
+            quote_log_value(&self.err_mesg)
+        );
+        for (key, value) in &self.context {
+            line.push_str(&format!(" ctx.{}={}", key, quote_log_value(value)));
         }
+        line
+    }
+
+    /// Formats the error like `Display` prefixed with its creation timestamp.
+    pub fn display_with_time(&self) -> String {
+        format!("[{}] {}", self.created_at, self)
+    }
+
+    /// Attaches a key-value pair to the error, keeping any previously attached pairs.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{ErrorArrayItem, Errors};
+    ///
+    /// let err = ErrorArrayItem::new(Errors::OpeningFile, "Failed to open file")
+    ///     .context("path", "/etc/app.conf")
+    ///     .context("uid", 1000);
+    /// assert_eq!(err.get_context("uid").unwrap().as_str(), "1000");
+    /// ```
+    pub fn context<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: ToString,
+    {
+        self.context
+            .push((Stringy::from(key), Stringy::from(value.to_string())));
+        self
+    }
+
+    /// Prefixes the message with `ctx`, separated by `": "`.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::ErrorArrayItem;
+    ///
+    /// let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file or directory");
+    /// let err = ErrorArrayItem::from(io_err).with_context("opening config at /etc/app.conf");
+    /// assert_eq!(
+    ///     err.err_mesg.as_str(),
+    ///     "opening config at /etc/app.conf: No such file or directory"
+    /// );
+    /// ```
+    pub fn with_context<S: Into<String>>(mut self, ctx: S) -> Self {
+        self.err_mesg = Stringy::from(format!("{}: {}", ctx.into(), self.err_mesg));
+        self
+    }
+
+    /// Same as [`ErrorArrayItem::with_context`], named to match [`ResultExt::wrap_err`].
+    pub fn wrap<M: Into<String>>(self, msg: M) -> ErrorArrayItem {
+        self.with_context(msg)
+    }
+
+    /// Returns the first value attached under `key`, if any.
+    pub fn get_context(&self, key: &str) -> Option<&Stringy> {
+        self.context
+            .iter()
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, v)| v)
+    }
+}
+
+/// Wraps an error so it hashes and compares by kind only, see [`ErrorArrayItem::same_kind`].
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use dusa_collection_utils::errors::{ErrorArrayItem, Errors, KindKey};
+///
+/// let mut first_of_kind: HashSet<KindKey> = HashSet::new();
+/// first_of_kind.insert(KindKey(ErrorArrayItem::new(Errors::NotFound, "a.txt")));
+/// first_of_kind.insert(KindKey(ErrorArrayItem::new(Errors::NotFound, "b.txt")));
+///
+/// assert_eq!(first_of_kind.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KindKey(pub ErrorArrayItem);
+
+impl PartialEq for KindKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.same_kind(&other.0)
+    }
+}
+
+impl Eq for KindKey {}
+
+impl std::hash::Hash for KindKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.err_type.hash(state);
+    }
+}
+
+impl From<ErrorArrayItem> for KindKey {
+    fn from(item: ErrorArrayItem) -> Self {
+        KindKey(item)
+    }
+}
+
+/// Extension methods turning a result's error into an [`ErrorArrayItem`].
+///
+/// `err_kind` and `err_context` accept any error that implements `Display` and
+/// replace the usual `map_err(|e| ErrorArrayItem::new(kind, e.to_string()))` closure.
+///
+/// ```rust
+/// use dusa_collection_utils::errors::{ErrorArrayItem, Errors, ResultExt};
+///
+/// fn read_config() -> Result<String, ErrorArrayItem> {
+///     Err(ErrorArrayItem::new(Errors::OpeningFile, "No such file or directory"))
+/// }
+///
+/// let err = read_config().wrap_err("while loading config").unwrap_err();
+/// assert_eq!(err.err_type, Errors::OpeningFile);
+/// assert_eq!(
+///     err.err_mesg.as_str(),
+///     "while loading config: No such file or directory"
+/// );
+/// ```
+pub trait ResultExt<T, E> {
+    /// Prefixes the error's message with `msg`, keeping its kind, see
+    /// [`ErrorArrayItem::with_context`].
+    fn wrap_err<M: Into<String>>(self, msg: M) -> Result<T, ErrorArrayItem>
+    where
+        E: Into<ErrorArrayItem>;
+
+    /// Converts the error into an item of `kind` with the error's text as the message.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{Errors, ResultExt};
+    ///
+    /// let err = "eighty".parse::<u16>().err_kind(Errors::ConfigParsing).unwrap_err();
+    /// assert_eq!(err.err_type, Errors::ConfigParsing);
+    /// assert_eq!(err.err_mesg.as_str(), "invalid digit found in string");
+    /// ```
+    fn err_kind(self, kind: Errors) -> Result<T, ErrorArrayItem>;
+
+    /// Like [`ResultExt::err_kind`], with the message prefixed by `msg`.
+    fn err_context(self, kind: Errors, msg: &str) -> Result<T, ErrorArrayItem>;
+}
+
+impl<T, E: fmt::Display> ResultExt<T, E> for Result<T, E> {
+    fn wrap_err<M: Into<String>>(self, msg: M) -> Result<T, ErrorArrayItem>
+    where
+        E: Into<ErrorArrayItem>,
+    {
+        self.map_err(|err| err.into().with_context(msg))
+    }
+
+    fn err_kind(self, kind: Errors) -> Result<T, ErrorArrayItem> {
+        self.map_err(|err| ErrorArrayItem::from_display(kind, err))
+    }
+
+    fn err_context(self, kind: Errors, msg: &str) -> Result<T, ErrorArrayItem> {
+        self.map_err(|err| ErrorArrayItem::new(kind, format!("{}: {}", msg, err)))
     }
 }
 
@@ -223,11 +1057,165 @@ impl ErrorArrayItem {
 pub struct WarningArray(pub Arc<RwLock<Vec<WarningArrayItem>>>);
 
 /// Represents a collection of errors.
+///
+/// Unbounded unless created with [`ErrorArray::with_capacity_limit`], clones share the
+/// errors and the limit.
+///
+/// The storage is private so the limit can not be bypassed. Since 4.0 the errors are
+/// read through methods such as [`ErrorArray::to_vec`] or [`ErrorArray::len`] instead
+/// of locking the field directly.
 #[derive(Debug, Clone)]
-pub struct ErrorArray(pub Arc<RwLock<Vec<ErrorArrayItem>>>);
+pub struct ErrorArray(pub(crate) Arc<RwLock<ErrorList>>);
+
+/// What a bounded [`ErrorArray`] does with errors pushed once it is full.
+///
+/// Whatever the policy, discarded errors are counted in a single `GeneralError` notice
+/// kept as the array's last item, see [`ErrorArray::with_capacity_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drops the oldest error to make room for the new one.
+    DropOldest,
+    /// Keeps the errors already collected and drops the new one.
+    DropNewest,
+    /// Like `DropNewest`, and the notice also counts the dropped errors per kind.
+    CollapseIntoCount,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CapacityLimit {
+    max: usize,
+    policy: OverflowPolicy,
+}
+
+// The errors behind an ErrorArray, with its capacity limit and the errors it discarded.
+// While `discarded` is non-zero the last item is the overflow notice, which only the
+// methods here add, rebuild or remove, so a user error is never taken for it.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorList {
+    items: VecDeque<ErrorArrayItem>,
+    limit: Option<CapacityLimit>,
+    discarded: usize,
+    discarded_by_kind: Vec<(Errors, usize)>,
+}
+
+// First context key of the notice a bounded ErrorArray keeps for discarded errors
+const OVERFLOW_KEY: &str = "discarded";
+
+impl ErrorList {
+    // Pushes `item`, applying the capacity limit if there is one
+    fn push(&mut self, item: ErrorArrayItem) {
+        let limit: CapacityLimit = match self.limit {
+            Some(limit) if self.real_len() >= limit.max => limit,
+            _ => return self.insert_before_notice(item),
+        };
+
+        // Rebuilt below with the new count
+        self.pop_notice();
+        let dropped: ErrorArrayItem = match limit.policy {
+            OverflowPolicy::DropOldest => match self.items.pop_front() {
+                Some(oldest) => {
+                    self.items.push_back(item);
+                    oldest
+                }
+                None => item,
+            },
+            OverflowPolicy::DropNewest | OverflowPolicy::CollapseIntoCount => item,
+        };
+
+        self.discarded += 1;
+        if limit.policy == OverflowPolicy::CollapseIntoCount {
+            match self
+                .discarded_by_kind
+                .iter_mut()
+                .find(|(kind, _)| *kind == dropped.err_type)
+            {
+                Some((_, count)) => *count += 1,
+                None => self.discarded_by_kind.push((dropped.err_type, 1)),
+            }
+        }
+        let notice: ErrorArrayItem = self.build_notice(limit);
+        self.items.push_back(notice);
+    }
+
+    fn insert_before_notice(&mut self, item: ErrorArrayItem) {
+        match self.discarded > 0 {
+            true => self.items.insert(self.items.len() - 1, item),
+            false => self.items.push_back(item),
+        }
+    }
+
+    // Number of items, not counting the notice
+    fn real_len(&self) -> usize {
+        self.items.len() - usize::from(self.discarded > 0)
+    }
+
+    // Removes the notice without resetting the counts
+    fn pop_notice(&mut self) -> Option<ErrorArrayItem> {
+        match self.discarded > 0 {
+            true => self.items.pop_back(),
+            false => None,
+        }
+    }
+
+    fn notice(&self) -> Option<&ErrorArrayItem> {
+        match self.discarded > 0 {
+            true => self.items.back(),
+            false => None,
+        }
+    }
+
+    // The GeneralError noting how many errors were discarded
+    fn build_notice(&self, limit: CapacityLimit) -> ErrorArrayItem {
+        let mut notice: ErrorArrayItem = ErrorArrayItem::new(
+            Errors::GeneralError,
+            format!(
+                "{} errors discarded, limit is {}",
+                self.discarded, limit.max
+            ),
+        )
+        .context(OVERFLOW_KEY, self.discarded.to_string());
+        for (kind, count) in &self.discarded_by_kind {
+            notice = notice.context(kind.as_str(), count.to_string());
+        }
+        notice
+    }
+
+    // Moves every item out, the notice last, and resets the counts
+    fn take_all(&mut self) -> Vec<ErrorArrayItem> {
+        self.reset_discarded();
+        std::mem::take(&mut self.items).into()
+    }
+
+    fn reset_discarded(&mut self) {
+        self.discarded = 0;
+        self.discarded_by_kind.clear();
+    }
+}
+
+impl std::ops::Deref for ErrorList {
+    type Target = VecDeque<ErrorArrayItem>;
+
+    fn deref(&self) -> &VecDeque<ErrorArrayItem> {
+        &self.items
+    }
+}
+
+// Same as WarningArray::new_container
+impl Default for WarningArray {
+    fn default() -> Self {
+        Self::new_container()
+    }
+}
+
+// Same as ErrorArray::new_container
+impl Default for ErrorArray {
+    fn default() -> Self {
+        Self::new_container()
+    }
+}
 
 /// Represents different types of generic warnings.
-#[derive(Debug, PartialEq, Clone, Copy, PartialOrd)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Warnings {
     /// Generic warning.
     Warning,
@@ -247,13 +1235,126 @@ pub enum Warnings {
     UnexpectedConfiguration,
 }
 
+impl Warnings {
+    /// Returns the relative weight of the warning, higher values are more severe.
+    ///
+    /// The ordering from most to least severe is:
+    /// `ResourceExhaustion` (7), `ConnectionLost` (6), `UnexpectedBehavior` (5),
+    /// `FileNotDeleted` (4), `MisAlignedChunk` (3), `UnexpectedConfiguration` (2),
+    /// `OutdatedVersion` (1) and the generic `Warning` (0).
+    pub fn weight(&self) -> u8 {
+        match self {
+            Warnings::ResourceExhaustion => 7,
+            Warnings::ConnectionLost => 6,
+            Warnings::UnexpectedBehavior => 5,
+            Warnings::FileNotDeleted => 4,
+            Warnings::MisAlignedChunk => 3,
+            Warnings::UnexpectedConfiguration => 2,
+            Warnings::OutdatedVersion => 1,
+            Warnings::Warning => 0,
+        }
+    }
+
+    /// Buckets the warning into a [`WarningSeverity`], consistent with [`Warnings::weight`].
+    pub fn severity(&self) -> WarningSeverity {
+        match self {
+            Warnings::ResourceExhaustion
+            | Warnings::ConnectionLost
+            | Warnings::UnexpectedBehavior => WarningSeverity::Major,
+            Warnings::FileNotDeleted
+            | Warnings::MisAlignedChunk
+            | Warnings::UnexpectedConfiguration => WarningSeverity::Minor,
+            Warnings::OutdatedVersion | Warnings::Warning => WarningSeverity::Info,
+        }
+    }
+
+    /// The error kind a warning becomes when it is escalated, see [`WarningArray::escalate`].
+    ///
+    /// | Warning                   | Error              |
+    /// |---------------------------|--------------------|
+    /// | `Warning`                 | `GeneralError`     |
+    /// | `OutdatedVersion`         | `AppState`         |
+    /// | `MisAlignedChunk`         | `InvalidChunkData` |
+    /// | `FileNotDeleted`          | `DeletingFile`     |
+    /// | `ConnectionLost`          | `ConnectionError`  |
+    /// | `ResourceExhaustion`      | `OverRamLimit`     |
+    /// | `UnexpectedBehavior`      | `GeneralError`     |
+    /// | `UnexpectedConfiguration` | `ConfigParsing`    |
+    pub fn error_kind(&self) -> Errors {
+        match self {
+            Warnings::Warning => Errors::GeneralError,
+            Warnings::OutdatedVersion => Errors::AppState,
+            Warnings::MisAlignedChunk => Errors::InvalidChunkData,
+            Warnings::FileNotDeleted => Errors::DeletingFile,
+            Warnings::ConnectionLost => Errors::ConnectionError,
+            Warnings::ResourceExhaustion => Errors::OverRamLimit,
+            Warnings::UnexpectedBehavior => Errors::GeneralError,
+            Warnings::UnexpectedConfiguration => Errors::ConfigParsing,
+        }
+    }
+}
+
+/// How much attention a warning needs, ordered from `Info` to `Major`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum WarningSeverity {
+    /// Worth knowing, nothing to act on.
+    Info,
+    /// Something was skipped or worked around.
+    Minor,
+    /// The program is degraded until the cause is fixed.
+    Major,
+}
+
+// Human readable text, used by WarningArrayItem's Display
+impl fmt::Display for Warnings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: &str = match self {
+            Warnings::Warning => "generic warning",
+            Warnings::OutdatedVersion => "outdated version",
+            Warnings::MisAlignedChunk => "misaligned chunk",
+            Warnings::FileNotDeleted => "file not deleted",
+            Warnings::ConnectionLost => "connection lost",
+            Warnings::ResourceExhaustion => "resource exhaustion",
+            Warnings::UnexpectedBehavior => "unexpected behavior",
+            Warnings::UnexpectedConfiguration => "unexpected configuration",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl fmt::Display for WarningSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: &str = match self {
+            WarningSeverity::Info => "info",
+            WarningSeverity::Minor => "minor",
+            WarningSeverity::Major => "major",
+        };
+        write!(f, "{}", text)
+    }
+}
+
 /// Represents a generic warning.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WarningArrayItem {
     /// Type of the warning.
     pub warn_type: Warnings,
     /// Optional message associated with the warning.
-    pub warn_mesg: Option<String>,
+    pub warn_mesg: Option<Stringy>,
+    /// Unix timestamp (seconds) of when the warning was created, 0 if unknown.
+    #[serde(default)]
+    pub created_at: u64,
+    /// How many identical warnings this item stands for, see
+    /// [`WarningArray::dedup_with_count`]. Always at least 1.
+    #[serde(default = "single_warning", skip_serializing_if = "is_single_warning")]
+    pub count: usize,
+}
+
+fn single_warning() -> usize {
+    1
+}
+
+fn is_single_warning(count: &usize) -> bool {
+    *count == 1
 }
 
 impl WarningArrayItem {
@@ -262,19 +1363,82 @@ impl WarningArrayItem {
         WarningArrayItem {
             warn_type: kind,
             warn_mesg: None,
+            created_at: current_timestamp(),
+            count: 1,
         }
     }
 
     /// Creates a new `WarningArrayItem` instance with details.
-    pub fn new_details(kind: Warnings, message: String) -> Self {
+    pub fn new_details<M>(kind: Warnings, message: M) -> Self
+    where
+        M: Into<String>,
+    {
         WarningArrayItem {
             warn_type: kind,
-            warn_mesg: Some(message),
+            warn_mesg: Some(Stringy::from(message)),
+            created_at: current_timestamp(),
+            count: 1,
+        }
+    }
+
+    /// Prefixes the message with `ctx`, separated by `": "`.
+    /// A warning without a message takes `ctx` as its message.
+    pub fn with_context<S: Into<String>>(mut self, ctx: S) -> Self {
+        let ctx: String = ctx.into();
+        self.warn_mesg = Some(match self.warn_mesg {
+            Some(message) => Stringy::from(format!("{}: {}", ctx, message)),
+            None => Stringy::from(ctx),
+        });
+        self
+    }
+}
+
+// Downgrades an error that turned out not to be fatal. The warning keeps the error's
+// creation time and its Plain rendering as the message, the kind follows its category.
+impl From<ErrorArrayItem> for WarningArrayItem {
+    fn from(err: ErrorArrayItem) -> Self {
+        let kind: Warnings = match (err.err_type, err.category()) {
+            (Errors::DeletingFile | Errors::DeletingDirectory, _) => Warnings::FileNotDeleted,
+            (_, ErrorCategory::Network) => Warnings::ConnectionLost,
+            (_, ErrorCategory::Resource) => Warnings::ResourceExhaustion,
+            (_, ErrorCategory::Config) => Warnings::UnexpectedConfiguration,
+            _ => Warnings::UnexpectedBehavior,
+        };
+        let mut warning: WarningArrayItem =
+            WarningArrayItem::new_details(kind, err.format_with(DisplayStyle::Plain));
+        warning.created_at = err.created_at;
+        warning
+    }
+}
+
+// Escalates a warning, see Warnings::error_kind. The message falls back to the
+// warning's kind and notes how many warnings a collapsed item stood for.
+impl From<WarningArrayItem> for ErrorArrayItem {
+    fn from(warning: WarningArrayItem) -> Self {
+        let mut message: String = match &warning.warn_mesg {
+            Some(mesg) => mesg.to_string(),
+            None => warning.warn_type.to_string(),
+        };
+        if warning.count > 1 {
+            message.push_str(&format!(" (x{})", warning.count));
         }
+        let mut err: ErrorArrayItem = ErrorArrayItem::new(warning.warn_type.error_kind(), message);
+        err.created_at = warning.created_at;
+        err
     }
 }
 
 impl WarningArray {
+    // A panic while the lock was held can only leave the vec with or without an item,
+    // never half written, so a poisoned lock is recovered rather than propagated.
+    fn read_lock(&self) -> RwLockReadGuard<'_, Vec<WarningArrayItem>> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_lock(&self) -> RwLockWriteGuard<'_, Vec<WarningArrayItem>> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Creates a new `WarningArray` instance.
     pub fn new(mut data: Vec<WarningArrayItem>) -> Self {
         let warning_array: Vec<WarningArrayItem> = Vec::new();
@@ -282,7 +1446,7 @@ impl WarningArray {
             0: Arc::new(RwLock::new(warning_array)),
         };
 
-        let mut to_append = warning.0.write().unwrap();
+        let mut to_append = warning.write_lock();
         to_append.append(&mut data);
         drop(to_append);
         return warning;
@@ -297,94 +1461,714 @@ impl WarningArray {
     }
 
     /// Displays the warnings.
-    pub fn display(self) {
-        let mut warning_array = self.0.write().unwrap();
-        for warns in warning_array.as_slice() {
+    pub fn display(mut self) {
+        for warns in self.take().to_string_vec() {
+            log!(LogLevel::Warn, "{}", warns)
+        }
+    }
+
+    /// Formats the warnings one per line like [`WarningArray::display`] does,
+    /// returning the text instead of printing it.
+    pub fn display_to_string(mut self) -> String {
+        self.take().to_string()
+    }
+
+    /// Formats each warning without displaying or clearing any of them.
+    pub fn to_string_vec(&self) -> Vec<String> {
+        self.read_lock()
+            .iter()
+            .map(|warns| warns.to_string())
+            .collect()
+    }
+
+    /// Displays the warnings ordered from most to least severe.
+    pub fn display_sorted(self) {
+        for warns in self.display_sorted_to_string().lines() {
             log!(LogLevel::Warn, "{}", warns)
         }
-        warning_array.clear()
+    }
+
+    /// Formats the warnings like [`WarningArray::display_sorted`] does,
+    /// returning the text instead of printing it.
+    pub fn display_sorted_to_string(mut self) -> String {
+        self.sort_by_severity();
+        self.display_to_string()
+    }
+
+    /// Sorts the warnings from most to least severe, see [`Warnings::weight`].
+    /// Warnings of equal weight keep their insertion order.
+    pub fn sort_by_severity(&mut self) {
+        let mut warning_array = self.write_lock();
+        warning_array.sort_by_key(|item| std::cmp::Reverse(item.warn_type.weight()));
+    }
+
+    /// Returns a copy of the most severe warning in the collection.
+    /// If several warnings share the highest weight the earliest one is returned.
+    pub fn most_severe(&self) -> Option<WarningArrayItem> {
+        let warning_array = self.read_lock();
+        warning_array
+            .iter()
+            .rev()
+            .max_by_key(|item| item.warn_type.weight())
+            .cloned()
     }
 
     /// Pushes a new warning to the collection.
     pub fn push(&mut self, item: WarningArrayItem) {
-        let mut warning_array = self.0.write().unwrap();
+        let mut warning_array = self.write_lock();
         warning_array.push(item);
         drop(warning_array)
     }
 
-    pub fn append(&mut self, arr: Self) {
-        let mut warning_array = self.0.write().unwrap();
-        let mut donor_array = arr.0.write().unwrap();
-        warning_array.append(&mut donor_array);
-        drop(donor_array);
+    /// Moves the warnings of `arr` onto the end of this array.
+    pub fn append(&mut self, mut arr: Self) {
+        // Drained first so the two arrays are never locked at the same time, `arr` may
+        // be a clone of this array
+        let donated: Vec<WarningArrayItem> = arr.drain();
+        self.write_lock().extend(donated);
     }
 
     pub fn len(&self) -> usize {
-        let vec = self.0.read().unwrap(); // Lock the RwLock and get a read guard
+        let vec = self.read_lock(); // Lock the RwLock and get a read guard
         vec.len()
     }
+
+    /// Like `len`, but returns `None` instead of recovering if the lock is poisoned.
+    pub fn try_len(&self) -> Option<usize> {
+        self.0.read().ok().map(|vec| vec.len())
+    }
+
+    /// Returns `true` if the collection holds no warnings.
+    pub fn is_empty(&self) -> bool {
+        self.read_lock().is_empty()
+    }
+
+    /// Removes every warning without displaying it.
+    pub fn clear(&mut self) {
+        self.write_lock().clear();
+    }
+
+    /// Retains only the warnings for which `predicate` returns `true`.
+    /// A poisoned lock is recovered, like every other method does.
+    pub fn retain<F>(&mut self, predicate: F)
+    where
+        F: Fn(&WarningArrayItem) -> bool,
+    {
+        let mut warning_array = self.write_lock();
+        warning_array.retain(|item| predicate(item));
+    }
+
+    /// Collapses warnings with the same type and message into the first of them,
+    /// adding up their counts. `Display` shows a collapsed warning once with `(xN)`.
+    ///
+    /// Already collapsed items keep their counts, so arrays can be appended to each
+    /// other and collapsed again without losing occurrences.
+    pub fn dedup_with_count(&mut self) {
+        let mut warning_array = self.write_lock();
+        let mut collapsed: Vec<WarningArrayItem> = Vec::with_capacity(warning_array.len());
+        let mut seen: HashMap<(Warnings, Option<String>), usize> = HashMap::new();
+
+        for item in warning_array.drain(..) {
+            // Keyed on the text, a Mutable and an Immutable Stringy hash differently
+            let key = (item.warn_type, item.warn_mesg.as_deref().map(str::to_owned));
+            match seen.get(&key) {
+                Some(&index) => collapsed[index].count += item.count.max(1),
+                None => {
+                    seen.insert(key, collapsed.len());
+                    collapsed.push(item);
+                }
+            }
+        }
+        *warning_array = collapsed;
+    }
+
+    /// Moves the warnings out, leaving the collection empty for every clone.
+    pub fn drain(&mut self) -> Vec<WarningArrayItem> {
+        std::mem::take(&mut *self.write_lock())
+    }
+
+    /// Moves the warnings into a new, unshared `WarningArray`, see [`WarningArray::drain`].
+    pub fn take(&mut self) -> WarningArray {
+        WarningArray::new(self.drain())
+    }
+
+    /// Consumes the collection and returns the warnings it held.
+    /// Any other clone of this array is left empty rather than invalidated.
+    pub fn into_vec(self) -> Vec<WarningArrayItem> {
+        let mut warning_array = self.write_lock();
+        std::mem::take(&mut *warning_array)
+    }
+
+    /// Counts how many warnings of each type are in the collection.
+    /// Collapsed warnings count once per occurrence they stand for.
+    pub fn count_by_type(&self) -> HashMap<Warnings, usize> {
+        let warning_array = self.read_lock();
+        let mut counts: HashMap<Warnings, usize> = HashMap::new();
+        for item in warning_array.iter() {
+            *counts.entry(item.warn_type).or_insert(0) += item.count.max(1);
+        }
+        counts
+    }
+
+    /// Splits the warnings, converting those matching `predicate` into errors of the kind
+    /// given by [`Warnings::error_kind`]. Both arrays keep the original order.
+    ///
+    /// The warnings are moved out, so other clones of this array are left empty.
+    pub fn escalate<F>(mut self, predicate: F) -> (WarningArray, ErrorArray)
+    where
+        F: Fn(&WarningArrayItem) -> bool,
+    {
+        let (escalated, kept): (Vec<WarningArrayItem>, Vec<WarningArrayItem>) =
+            self.drain().into_iter().partition(|item| predicate(item));
+        (
+            WarningArray::new(kept),
+            escalated.into_iter().map(ErrorArrayItem::from).collect(),
+        )
+    }
+
+    /// Checks if a warning of the given type is present in the collection.
+    pub fn contains_type(&self, kind: Warnings) -> bool {
+        let warning_array = self.read_lock();
+        warning_array.iter().any(|item| item.warn_type == kind)
+    }
+}
+
+impl ErrorArray {
+    // See WarningArray::read_lock, a poisoned lock is recovered rather than propagated.
+    fn read_lock(&self) -> RwLockReadGuard<'_, ErrorList> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_lock(&self) -> RwLockWriteGuard<'_, ErrorList> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Creates a new `Errors` instance.
+    pub fn new(data: Vec<ErrorArrayItem>) -> Self {
+        let error_array: ErrorList = ErrorList {
+            items: VecDeque::from(data),
+            ..Default::default()
+        };
+        Self(Arc::new(RwLock::new(error_array)))
+    }
+
+    /// Creates an empty `Errors` instance.
+    pub fn new_container() -> Self {
+        let error_array: ErrorList = ErrorList::default();
+        Self(Arc::new(RwLock::new(error_array)))
+    }
+
+    /// Creates an empty array holding at most `max` errors, a `max` of zero is treated
+    /// as one. Pushing past the limit applies `policy`.
+    ///
+    /// Once errors are discarded the array keeps a single `GeneralError` notice as its
+    /// last item, updated on every further discard. The notice is an ordinary item for
+    /// `len`, display, serialization and the methods that move the errors out, so a
+    /// full array holds `max` errors plus the notice. Its first context entry is
+    /// `discarded` with the total, [`OverflowPolicy::CollapseIntoCount`] adds an entry
+    /// per kind. Moving the errors out, or removing the notice with `pop` or `retain`,
+    /// resets the count.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{ErrorArray, ErrorArrayItem, Errors, OverflowPolicy};
+    ///
+    /// let mut errors = ErrorArray::with_capacity_limit(2, OverflowPolicy::DropNewest);
+    /// for i in 0..5 {
+    ///     errors.push(ErrorArrayItem::new(Errors::NotFound, format!("file {}", i)));
+    /// }
+    ///
+    /// assert_eq!(errors.len(), 3);
+    /// assert_eq!(errors.discarded(), 3);
+    ///
+    /// let items = errors.into_vec();
+    /// assert_eq!(items[1].err_mesg.as_str(), "file 1");
+    /// assert_eq!(items[2].err_mesg.as_str(), "3 errors discarded, limit is 2");
+    /// ```
+    pub fn with_capacity_limit(max: usize, policy: OverflowPolicy) -> Self {
+        let error_array: ErrorList = ErrorList {
+            limit: Some(CapacityLimit {
+                max: max.max(1),
+                policy,
+            }),
+            ..Default::default()
+        };
+        Self(Arc::new(RwLock::new(error_array)))
+    }
+
+    /// Number of errors the capacity limit discarded since the errors were last moved
+    /// out. Always zero for an unbounded array.
+    pub fn discarded(&self) -> usize {
+        self.read_lock().discarded
+    }
+
+    /// A copy of the overflow notice, the array's last item while
+    /// [`ErrorArray::discarded`] is non-zero.
+    pub fn overflow_notice(&self) -> Option<ErrorArrayItem> {
+        self.read_lock().notice().cloned()
+    }
+
+    /// Returns a copy of the errors, overflow notice included, leaving them in place.
+    pub fn to_vec(&self) -> Vec<ErrorArrayItem> {
+        self.read_lock().iter().cloned().collect()
+    }
+
+    /// Logs the errors and clears the collection. Never exits the process.
+    ///
+    /// This replaces `display(die: bool)`. Callers that passed `false` can drop the
+    /// argument. Callers that passed `true` should return [`ErrorArray::into_exit_code`]
+    /// from `main`, or call [`ErrorArray::display_and_exit`] to keep the old behavior.
+    pub fn display(&self) {
+        for errors in self.clone().drain() {
+            log!(LogLevel::Error, "{}", errors);
+        }
+    }
+
+    /// Logs the errors and returns the exit code a process should finish with:
+    /// `FAILURE` if there were any errors, `SUCCESS` otherwise.
+    ///
+    /// ```rust
+    /// use std::process::ExitCode;
+    /// use dusa_collection_utils::errors::{ErrorArray, ErrorArrayItem, Errors};
+    ///
+    /// fn run() -> ExitCode {
+    ///     let mut errors = ErrorArray::new_container();
+    ///     errors.push(ErrorArrayItem::new(Errors::NotFound, "config missing"));
+    ///     errors.into_exit_code()
+    /// }
+    ///
+    /// assert_eq!(run(), ExitCode::FAILURE);
+    /// ```
+    pub fn into_exit_code(self) -> ExitCode {
+        let failed = !self.is_empty();
+        self.display();
+        match failed {
+            true => ExitCode::FAILURE,
+            false => ExitCode::SUCCESS,
+        }
+    }
+
+    /// Logs the errors and exits the process with status 1, skipping destructors.
+    #[deprecated(note = "use `display` and return `into_exit_code` from main instead")]
+    pub fn display_and_exit(self) -> ! {
+        self.display();
+        std::process::exit(1)
+    }
+
+    /// Formats the errors one per line like [`ErrorArray::display`] does,
+    /// returning the text instead of printing it. Never exits the process.
+    pub fn display_to_string(mut self) -> String {
+        self.take().to_string()
+    }
+
+    /// Formats each error without displaying or clearing any of them.
+    pub fn to_string_vec(&self) -> Vec<String> {
+        let error_array = self.read_lock();
+        error_array
+            .iter()
+            .map(|errors| errors.to_string())
+            .collect()
+    }
+
+    /// Logs the errors oldest first, each prefixed with its creation timestamp,
+    /// and clears the collection.
+    pub fn display_by_time(&self) {
+        let errors: Vec<ErrorArrayItem> = self.write_lock().take_all();
+        for line in lines_by_time(errors) {
+            log!(LogLevel::Error, "{}", line);
+        }
+    }
+
+    /// Formats the errors like [`ErrorArray::display_by_time`] does, one per line,
+    /// without displaying or clearing any of them.
+    pub fn display_by_time_to_string(&self) -> String {
+        let errors: Vec<ErrorArrayItem> = self.read_lock().iter().cloned().collect();
+        lines_by_time(errors).join("\n")
+    }
+
+    /// Pushes a new error to the collection.
+    pub fn push(&mut self, item: ErrorArrayItem) {
+        self.write_lock().push(item);
+    }
+
+    /// Pop the last error from the array. Popping the overflow notice resets the
+    /// discarded count.
+    pub fn pop(&mut self) -> ErrorArrayItem {
+        let mut error_array = self.write_lock();
+        let popped: Option<ErrorArrayItem> = error_array.items.pop_back();
+        error_array.reset_discarded();
+        popped.unwrap_or(ErrorArrayItem::new(
+            Errors::GeneralError,
+            String::from("No previous error"),
+        ))
+    }
+
+    /// Moves the errors of `arr` onto the end of this array, applying its capacity limit.
+    pub fn append(&mut self, mut arr: Self) {
+        // Drained first so the two arrays are never locked at the same time
+        let donated: Vec<ErrorArrayItem> = arr.drain();
+        self.extend(donated);
+    }
+
+    pub fn len(&self) -> usize {
+        let vec = self.read_lock(); // Lock the RwLock and get a read guard
+        vec.len()
+    }
+
+    /// Like `len`, but returns `None` instead of recovering if the lock is poisoned.
+    pub fn try_len(&self) -> Option<usize> {
+        self.0.read().ok().map(|vec| vec.len())
+    }
+
+    /// Returns `true` if the collection holds no errors.
+    pub fn is_empty(&self) -> bool {
+        self.read_lock().is_empty()
+    }
+
+    /// Removes every error without logging it, resetting the discarded count.
+    pub fn clear(&mut self) {
+        self.write_lock().take_all();
+    }
+
+    /// Retains only the errors for which `predicate` returns `true`.
+    /// The overflow notice is tested like any other item, dropping it resets the
+    /// discarded count. A poisoned lock is recovered, like every other method does.
+    pub fn retain<F>(&mut self, predicate: F)
+    where
+        F: Fn(&ErrorArrayItem) -> bool,
+    {
+        let mut error_array = self.write_lock();
+        let notice: Option<ErrorArrayItem> = error_array.pop_notice();
+        error_array.items.retain(|item| predicate(item));
+        match notice {
+            Some(notice) if predicate(&notice) => error_array.items.push_back(notice),
+            _ => error_array.reset_discarded(),
+        }
+    }
+
+    /// Moves the errors out, the overflow notice last, leaving the collection empty for
+    /// every clone and resetting the discarded count.
+    pub fn drain(&mut self) -> Vec<ErrorArrayItem> {
+        self.write_lock().take_all()
+    }
+
+    /// Moves the errors into a new, unshared `ErrorArray` with the same capacity limit.
+    /// The discarded count moves along with them.
+    pub fn take(&mut self) -> ErrorArray {
+        let mut error_array = self.write_lock();
+        let taken: ErrorList = ErrorList {
+            items: std::mem::take(&mut error_array.items),
+            limit: error_array.limit,
+            discarded: error_array.discarded,
+            discarded_by_kind: std::mem::take(&mut error_array.discarded_by_kind),
+        };
+        error_array.reset_discarded();
+        ErrorArray(Arc::new(RwLock::new(taken)))
+    }
+
+    /// Consumes the collection and returns the errors it held, see [`ErrorArray::drain`].
+    /// Any other clone of this array is left empty rather than invalidated.
+    pub fn into_vec(mut self) -> Vec<ErrorArrayItem> {
+        self.drain()
+    }
+
+    /// Counts how many errors of each type are in the collection.
+    pub fn count_by_type(&self) -> HashMap<Errors, usize> {
+        let error_array = self.read_lock();
+        let mut counts: HashMap<Errors, usize> = HashMap::new();
+        for item in error_array.iter() {
+            *counts.entry(item.err_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Alias of [`ErrorArray::count_by_type`], named after [`ErrorArray::contains_kind`].
+    pub fn counts_by_kind(&self) -> HashMap<Errors, usize> {
+        self.count_by_type()
+    }
+
+    /// Checks if an error of the given type is present in the collection.
+    pub fn contains_type(&self, kind: Errors) -> bool {
+        let error_array = self.read_lock();
+        error_array.iter().any(|item| item.err_type == kind)
+    }
+
+    /// Same as [`ErrorArray::contains_type`], named to match [`ErrorArray::filter_by_kind`].
+    pub fn contains_kind(&self, kind: Errors) -> bool {
+        self.contains_type(kind)
+    }
+
+    /// Returns copies of all errors of the given type, in insertion order.
+    pub fn filter_by_kind(&self, kind: Errors) -> Vec<ErrorArrayItem> {
+        let error_array = self.read_lock();
+        error_array
+            .iter()
+            .filter(|item| item.err_type == kind)
+            .cloned()
+            .collect()
+    }
+
+    /// Retains only the errors of the given type.
+    pub fn retain_kind(&mut self, kind: Errors) {
+        self.retain(|item| item.err_type == kind)
+    }
+
+    /// Returns a copy of the first error of the given type, if any.
+    pub fn find_first(&self, kind: Errors) -> Option<ErrorArrayItem> {
+        let error_array = self.read_lock();
+        error_array
+            .iter()
+            .find(|item| item.err_type == kind)
+            .cloned()
+    }
+
+    /// Summarizes the errors grouped by kind, most frequent first, with up to
+    /// [`REPORT_EXAMPLES`] example messages per kind. Nothing is logged or cleared.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{ErrorArray, ErrorArrayItem, Errors};
+    ///
+    /// let errors = ErrorArray::new(vec![
+    ///     ErrorArrayItem::new(Errors::Timeout, "db"),
+    ///     ErrorArrayItem::new(Errors::NotFound, "a.txt"),
+    ///     ErrorArrayItem::new(Errors::NotFound, "b.txt"),
+    /// ]);
+    /// assert_eq!(
+    ///     errors.report(),
+    ///     "3 errors of 2 kinds\nNotFound x2\n  - a.txt\n  - b.txt\nTimeout x1\n  - db"
+    /// );
+    /// ```
+    pub fn report(&self) -> Stringy {
+        self.report_with(REPORT_EXAMPLES, None)
+    }
+
+    /// Same as [`ErrorArray::report`] with `examples` messages per kind. If the report is
+    /// longer than `max_len` bytes, whole lines are kept while they fit and a
+    /// `... truncated` line is appended.
+    pub fn report_with(&self, examples: usize, max_len: Option<usize>) -> Stringy {
+        let error_array = self.read_lock();
+
+        let mut groups: Vec<(Errors, Vec<&Stringy>)> = Vec::new();
+        for item in error_array.iter() {
+            match groups.iter_mut().find(|(kind, _)| *kind == item.err_type) {
+                Some((_, messages)) => messages.push(&item.err_mesg),
+                None => groups.push((item.err_type, vec![&item.err_mesg])),
+            }
+        }
+        // Stable sort, kinds with equal counts keep the order they first appeared in
+        groups.sort_by_key(|(_, messages)| std::cmp::Reverse(messages.len()));
+
+        let mut lines: Vec<String> = vec![format!(
+            "{} errors of {} kinds",
+            error_array.len(),
+            groups.len()
+        )];
+        for (kind, messages) in &groups {
+            lines.push(format!("{:?} x{}", kind, messages.len()));
+            for message in messages.iter().take(examples) {
+                lines.push(format!("  - {}", message));
+            }
+            if messages.len() > examples {
+                lines.push(format!("  ... and {} more", messages.len() - examples));
+            }
+        }
+        drop(error_array);
+
+        let report: String = lines.join("\n");
+        match max_len {
+            Some(max_len) if report.len() > max_len => {
+                let mut truncated: String = String::new();
+                for line in &lines {
+                    if truncated.len() + line.len() + 1 > max_len {
+                        break;
+                    }
+                    truncated.push_str(line);
+                    truncated.push('\n');
+                }
+                truncated.push_str("... truncated");
+                Stringy::from(truncated)
+            }
+            _ => Stringy::from(report),
+        }
+    }
+}
+
+/// Example messages per kind in [`ErrorArray::report`].
+pub const REPORT_EXAMPLES: usize = 3;
+
+// Orders the errors oldest first, keeping insertion order for equal timestamps
+fn lines_by_time(mut errors: Vec<ErrorArrayItem>) -> Vec<String> {
+    errors.sort_by_key(|item| item.created_at);
+    errors.iter().map(|item| item.display_with_time()).collect()
+}
+
+// Writes the items one per line under a single read lock
+fn write_lines<I>(f: &mut fmt::Formatter<'_>, items: I) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            writeln!(f)?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+// Renders the errors one per line, or ErrorArray::report with `{:#}`.
+// Either way the errors are left in place.
+impl fmt::Display for ErrorArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.alternate() {
+            true => write!(f, "{}", self.report()),
+            false => write_lines(f, self.read_lock().iter()),
+        }
+    }
+}
+
+// Renders the warnings one per line, leaving them in place
+impl fmt::Display for WarningArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_lines(f, self.read_lock().iter())
+    }
+}
+
+/// Collection of errors for async code, backed by `tokio::sync::RwLock`.
+///
+/// Waiting for the lock yields to the executor instead of blocking a worker thread.
+/// Clones share the same errors, like [`ErrorArray`]. Convert with `From<ErrorArray>`
+/// and [`AsyncErrorArray::into_error_array`] at the boundary with sync code.
+#[derive(Debug, Clone, Default)]
+pub struct AsyncErrorArray(pub Arc<tokio::sync::RwLock<Vec<ErrorArrayItem>>>);
+
+impl AsyncErrorArray {
+    /// Creates an `AsyncErrorArray` holding `data`.
+    pub fn new(data: Vec<ErrorArrayItem>) -> Self {
+        Self(Arc::new(tokio::sync::RwLock::new(data)))
+    }
+
+    /// Creates an empty `AsyncErrorArray`.
+    pub fn new_container() -> Self {
+        Self::default()
+    }
+
+    /// Pushes a new error to the collection.
+    pub async fn push(&self, item: ErrorArrayItem) {
+        self.0.write().await.push(item);
+    }
+
+    /// Returns the number of errors in the collection.
+    pub async fn len(&self) -> usize {
+        self.0.read().await.len()
+    }
+
+    /// Returns `true` if the collection holds no errors.
+    pub async fn is_empty(&self) -> bool {
+        self.0.read().await.is_empty()
+    }
+
+    /// Logs the errors and clears the collection, like [`ErrorArray::display`].
+    pub async fn display(&self) {
+        let mut error_array = self.0.write().await;
+        for errors in error_array.iter() {
+            log!(LogLevel::Error, "{}", errors);
+        }
+        error_array.clear()
+    }
+
+    /// Moves the errors into a sync [`ErrorArray`].
+    /// Any other clone of this array is left empty.
+    pub async fn into_error_array(self) -> ErrorArray {
+        let mut error_array = self.0.write().await;
+        ErrorArray::new(std::mem::take(&mut *error_array))
+    }
+}
+
+// Moves the errors out of the sync array, other clones of it are left empty
+impl From<ErrorArray> for AsyncErrorArray {
+    fn from(errors: ErrorArray) -> Self {
+        AsyncErrorArray::new(errors.into_vec())
+    }
+}
+
+// Drains the array under a single write lock and iterates over the owned errors
+impl IntoIterator for ErrorArray {
+    type Item = ErrorArrayItem;
+    type IntoIter = std::vec::IntoIter<ErrorArrayItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl FromIterator<ErrorArrayItem> for ErrorArray {
+    fn from_iter<I: IntoIterator<Item = ErrorArrayItem>>(iter: I) -> Self {
+        ErrorArray::new(iter.into_iter().collect())
+    }
 }
 
-impl ErrorArray {
-    /// Creates a new `Errors` instance.
-    pub fn new(mut data: Vec<ErrorArrayItem>) -> Self {
-        let error_array: Vec<ErrorArrayItem> = Vec::with_capacity(2);
-        let error: ErrorArray = Self {
-            0: Arc::new(RwLock::new(error_array)),
-        };
-
-        let mut to_append = error.0.write().unwrap();
-        to_append.append(&mut data);
-        drop(to_append);
-        return error;
+// The items are collected before the write lock is taken, once, so an iterator that
+// reads from a clone of this array can not deadlock against it
+impl Extend<ErrorArrayItem> for ErrorArray {
+    fn extend<I: IntoIterator<Item = ErrorArrayItem>>(&mut self, iter: I) {
+        let items: Vec<ErrorArrayItem> = iter.into_iter().collect();
+        let mut error_array = self.write_lock();
+        for item in items {
+            error_array.push(item);
+        }
     }
+}
 
-    /// Creates an empty `Errors` instance.
-    pub fn new_container() -> Self {
-        let error_array: Vec<ErrorArrayItem> = Vec::new();
-        Self {
-            0: Arc::new(RwLock::new(error_array)),
-        }
+impl FromIterator<WarningArrayItem> for WarningArray {
+    fn from_iter<I: IntoIterator<Item = WarningArrayItem>>(iter: I) -> Self {
+        WarningArray::new(iter.into_iter().collect())
     }
+}
 
-    /// Displays the errors.
-    pub fn display(self, die: bool) {
-        let mut error_array = self.0.write().unwrap();
-        for errors in error_array.as_slice() {
-            log!(LogLevel::Error, "{}", errors);
-        }
-        if die {
-            std::process::exit(1);
-        } else {
-            error_array.clear()
-        }
+// Same single write lock as the ErrorArray impl
+impl Extend<WarningArrayItem> for WarningArray {
+    fn extend<I: IntoIterator<Item = WarningArrayItem>>(&mut self, iter: I) {
+        let items: Vec<WarningArrayItem> = iter.into_iter().collect();
+        self.write_lock().extend(items);
     }
+}
 
-    /// Pushes a new error to the collection.
-    pub fn push(&mut self, item: ErrorArrayItem) {
-        let mut error_array = self.0.write().unwrap();
-        error_array.push(item);
+// Serializes as a plain array of items, recovering a poisoned lock.
+impl Serialize for ErrorArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.read_lock().serialize(serializer)
     }
+}
 
-    /// Pop the last error from the array
-    pub fn pop(&mut self) -> ErrorArrayItem {
-        let mut error_array = self.0.write().unwrap();
-        error_array.pop().unwrap_or(ErrorArrayItem::new(
-            Errors::GeneralError,
-            String::from("No previous error"),
-        ))
+impl<'de> Deserialize<'de> for ErrorArray {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<ErrorArrayItem>::deserialize(deserializer).map(ErrorArray::new)
     }
+}
 
-    pub fn append(&mut self, arr: Self) {
-        let mut error_array = self.0.write().unwrap();
-        let mut donor_array = arr.0.write().unwrap();
-        error_array.append(&mut donor_array);
-        drop(donor_array);
+// Serializes as a plain array of items, recovering a poisoned lock.
+impl Serialize for WarningArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.read_lock().serialize(serializer)
     }
+}
 
-    pub fn len(&self) -> usize {
-        let vec = self.0.read().unwrap(); // Lock the RwLock and get a read guard
-        vec.len()
+impl<'de> Deserialize<'de> for WarningArray {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<WarningArrayItem>::deserialize(deserializer).map(WarningArray::new)
     }
 }
 
@@ -398,25 +2182,189 @@ pub enum UnifiedResult<T> {
 }
 
 /// Represents a result that contains data and warnings.
-#[derive(Debug)]
+///
+/// Serializes as `{ "data": ..., "warnings": [...] }`.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct OkWarning<T> {
     /// Data associated with the result.
     pub data: T,
     /// Warnings associated with the result.
+    #[serde(rename = "warnings")]
     pub warning: WarningArray,
 }
 
+// Wire shape of a UnifiedResult, tagged by `status`. The borrowed form serializes,
+// the owned form deserializes.
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum UnifiedWireRef<'a, T> {
+    Ok {
+        data: &'a T,
+    },
+    OkWarn {
+        data: &'a T,
+        warnings: &'a WarningArray,
+    },
+    Err {
+        error: &'a ErrorArrayItem,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum UnifiedWire<T> {
+    Ok { data: T },
+    OkWarn { data: T, warnings: WarningArray },
+    Err { error: ErrorArrayItem },
+}
+
+// Serializes as `{"status": "ok", "data": ...}`, `{"status": "ok_warn", "data": ...,
+// "warnings": [...]}` or `{"status": "err", "error": {...}}`. An error carries no
+// warnings, so both variants' errors share the `err` shape and come back as
+// `ResultNoWarns`.
+impl<T: Serialize> Serialize for UnifiedResult<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let wire: UnifiedWireRef<'_, T> = match self {
+            UnifiedResult::ResultNoWarns(Ok(data)) => UnifiedWireRef::Ok { data },
+            UnifiedResult::ResultWarning(Ok(ok)) => UnifiedWireRef::OkWarn {
+                data: &ok.data,
+                warnings: &ok.warning,
+            },
+            UnifiedResult::ResultWarning(Err(error)) | UnifiedResult::ResultNoWarns(Err(error)) => {
+                UnifiedWireRef::Err { error }
+            }
+        };
+        wire.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for UnifiedResult<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(match UnifiedWire::<T>::deserialize(deserializer)? {
+            UnifiedWire::Ok { data } => UnifiedResult::ResultNoWarns(Ok(data)),
+            UnifiedWire::OkWarn { data, warnings } => UnifiedResult::ResultWarning(Ok(OkWarning {
+                data,
+                warning: warnings,
+            })),
+            UnifiedWire::Err { error } => UnifiedResult::ResultNoWarns(Err(error)),
+        })
+    }
+}
+
 /// returns the data within the OkWarning<T>
 /// This consumes the warning
 impl<T> OkWarning<T> {
     /// returns the data within the OkWarning<T>
-    /// This consumes the warning
+    /// This consumes the warning, displaying and clearing it. Use [`OkWarning::strip_with`]
+    /// to keep the warnings instead.
     pub fn strip(self) -> T {
         let ok_warning: OkWarning<T> = self;
         ok_warning.warning.display();
         ok_warning.data
     }
 
+    /// Maps the data with `f`, keeping the same warning array.
+    pub fn map<U, F>(self, f: F) -> OkWarning<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        OkWarning {
+            data: f(self.data),
+            warning: self.warning,
+        }
+    }
+
+    /// Adds a warning and returns the OkWarning, for building one up fluently.
+    pub fn add_warning(mut self, item: WarningArrayItem) -> Self {
+        self.warning.push(item);
+        self
+    }
+
+    /// Adds a warning in place, for when the OkWarning is held in a variable.
+    pub fn push_warning(&mut self, item: WarningArrayItem) {
+        self.warning.push(item);
+    }
+
+    /// Pairs the data of both OkWarnings, moving the warnings of `other` after those of
+    /// `self`. Nothing is displayed or cleared.
+    pub fn merge<U>(mut self, other: OkWarning<U>) -> OkWarning<(T, U)> {
+        self.warning.append(other.warning);
+        OkWarning {
+            data: (self.data, other.data),
+            warning: self.warning,
+        }
+    }
+
+    /// Fails the result if any warning matches `predicate`, see [`WarningArray::escalate`].
+    ///
+    /// A single escalated warning becomes the error. Several are combined into one error
+    /// with the kind of the first, listing each of them. Otherwise the data is returned
+    /// with the remaining warnings, none of which are displayed.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{
+    ///     Errors, OkWarning, WarningArray, WarningArrayItem, Warnings,
+    /// };
+    ///
+    /// let warnings = WarningArray::new(vec![
+    ///     WarningArrayItem::new_details(Warnings::ResourceExhaustion, "disk 91%"),
+    ///     WarningArrayItem::new_details(Warnings::ResourceExhaustion, "disk 97%"),
+    ///     WarningArrayItem::new(Warnings::OutdatedVersion),
+    /// ]);
+    ///
+    /// // Fail once more than one resource warning piled up
+    /// let exhausted = warnings.count_by_type().get(&Warnings::ResourceExhaustion).copied();
+    /// let result = OkWarning { data: (), warning: warnings }.fail_if(|warning| {
+    ///     warning.warn_type == Warnings::ResourceExhaustion && exhausted > Some(1)
+    /// });
+    ///
+    /// let err = result.get_err().unwrap();
+    /// assert_eq!(err.err_type, Errors::OverRamLimit);
+    /// assert_eq!(err.err_mesg.as_str(), "2 warnings escalated: disk 91%; disk 97%");
+    /// ```
+    pub fn fail_if<F>(self, predicate: F) -> UnifiedResult<T>
+    where
+        F: Fn(&WarningArrayItem) -> bool,
+    {
+        let (kept, escalated) = self.warning.escalate(predicate);
+        let mut escalated: Vec<ErrorArrayItem> = escalated.into_vec();
+
+        match escalated.len() {
+            0 => UnifiedResult::new_warn(Ok(OkWarning {
+                data: self.data,
+                warning: kept,
+            })),
+            1 => UnifiedResult::new_warn(Err(escalated.remove(0))),
+            count => {
+                let messages: Vec<String> = escalated
+                    .iter()
+                    .map(|err| err.err_mesg.to_string())
+                    .collect();
+                UnifiedResult::new_warn(Err(ErrorArrayItem::new(
+                    escalated[0].err_type,
+                    format!("{} warnings escalated: {}", count, messages.join("; ")),
+                )))
+            }
+        }
+    }
+
+    /// Counterpart of [`OkWarning::strip`] that returns the warnings instead of
+    /// displaying them, same as [`OkWarning::into_parts`].
+    pub fn strip_with(self) -> (T, WarningArray) {
+        self.into_parts()
+    }
+
+    /// Splits the OkWarning into its data and warnings without displaying them.
+    pub fn into_parts(self) -> (T, WarningArray) {
+        (self.data, self.warning)
+    }
+
     /// new_none wraps the associated T into a OkWarning<T> and the warning field is a empty warning array container.
     pub fn new_none(value: T) -> Self {
         OkWarning {
@@ -447,30 +2395,66 @@ impl<T> UnifiedResult<T> {
     }
 
     /// Resolves the `UnifiedResult` and returns the data if successful.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the error's message if the result is an error, like `Result::unwrap`.
     pub fn unwrap(self) -> T {
+        match self.uf_unwrap() {
+            Ok(d) => d,
+            Err(e) => panic!("called `UnifiedResult::unwrap()` on an error: {}", e),
+        }
+    }
+
+    /// Resolves the `UnifiedResult` and returns the data if successful.
+    ///
+    /// # Panics
+    ///
+    /// Logs `msg` together with the error and panics with the same text if the result is
+    /// an error. The panic location is the caller's.
+    ///
+    /// ```rust,should_panic
+    /// use dusa_collection_utils::errors::{ErrorArrayItem, Errors, UnifiedResult};
+    ///
+    /// let result: UnifiedResult<u32> =
+    ///     UnifiedResult::new(Err(ErrorArrayItem::new(Errors::NotFound, "/etc/app.conf")));
+    /// // panics with "loading config: NotFound: /etc/app.conf"
+    /// result.expect("loading config");
+    /// ```
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        match self.uf_unwrap() {
+            Ok(d) => d,
+            Err(e) => {
+                log!(LogLevel::Error, "{}: {}", msg, e);
+                panic!("{}: {}", msg, e)
+            }
+        }
+    }
+
+    /// Like [`UnifiedResult::expect`], but also panics if any warnings were produced.
+    /// The warnings are included in the panic message.
+    #[track_caller]
+    pub fn expect_warn(self, msg: &str) -> T {
         match self {
-            UnifiedResult::ResultWarning(r) => match r {
-                Ok(d) => {
-                    d.warning.display();
-                    return d.data;
-                }
-                Err(e) => {
-                    ErrorArray::new(vec![e]).display(true);
-                    unreachable!()
-                }
-            },
-            UnifiedResult::ResultNoWarns(r) => match r {
-                Ok(d) => return d,
-                Err(e) => {
-                    ErrorArray::new(vec![e]).display(true);
-                    unreachable!()
-                }
-            },
+            UnifiedResult::ResultWarning(Ok(d)) if !d.warning.is_empty() => {
+                let warnings: String = d.warning.display_to_string();
+                log!(
+                    LogLevel::Error,
+                    "{}: unexpected warnings: {}",
+                    msg,
+                    warnings
+                );
+                panic!("{}: unexpected warnings: {}", msg, warnings)
+            }
+            result => result.expect(msg),
         }
     }
 
     /// Unwraps the `UnifiedResult` and returns the data or errors.
-    /// This function will display any warnings and empty the warning array
+    /// This function will display any warnings and empty the warning array, which also
+    /// empties every clone of it. Use [`UnifiedResult::uf_unwrap_with_warnings`] to pass
+    /// the warnings on instead.
     pub fn uf_unwrap(self) -> Result<T, ErrorArrayItem> {
         match self {
             UnifiedResult::ResultWarning(r) => match r {
@@ -486,6 +2470,93 @@ impl<T> UnifiedResult<T> {
         }
     }
 
+    /// Returns the data, or `default` if the result is an error.
+    /// Warnings are displayed like [`UnifiedResult::uf_unwrap`] does, this never panics.
+    pub fn unwrap_or(self, default: T) -> T {
+        self.uf_unwrap().unwrap_or(default)
+    }
+
+    /// Returns the data, or computes a fallback from the error with `f`.
+    /// Warnings are displayed like [`UnifiedResult::uf_unwrap`] does, this never panics.
+    pub fn unwrap_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce(ErrorArrayItem) -> T,
+    {
+        self.uf_unwrap().unwrap_or_else(f)
+    }
+
+    /// Returns the data, or `T::default()` if the result is an error.
+    /// Warnings are displayed like [`UnifiedResult::uf_unwrap`] does, this never panics.
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.uf_unwrap().unwrap_or_default()
+    }
+
+    /// Borrows the warnings of a successful `ResultWarning` without displaying or
+    /// clearing them.
+    pub fn warnings(&self) -> Option<&WarningArray> {
+        match self {
+            UnifiedResult::ResultWarning(Ok(d)) => Some(&d.warning),
+            _ => None,
+        }
+    }
+
+    /// Number of warnings [`UnifiedResult::warnings`] would return, zero if there are none.
+    pub fn warning_count(&self) -> usize {
+        self.warnings().map_or(0, WarningArray::len)
+    }
+
+    /// Moves the warnings out, leaving an empty array in their place.
+    /// Returns an empty array if the result carries no warnings.
+    pub fn take_warnings(&mut self) -> WarningArray {
+        match self {
+            UnifiedResult::ResultWarning(Ok(d)) => std::mem::take(&mut d.warning),
+            _ => WarningArray::new_container(),
+        }
+    }
+
+    /// Calls `f` with the ok value, if any, and returns `self` unchanged, warnings included.
+    pub fn inspect<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T),
+    {
+        match &self {
+            UnifiedResult::ResultWarning(Ok(d)) => f(&d.data),
+            UnifiedResult::ResultNoWarns(Ok(d)) => f(d),
+            _ => (),
+        }
+        self
+    }
+
+    /// Calls `f` with the error, if any, and returns `self` unchanged.
+    pub fn inspect_err<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&ErrorArrayItem),
+    {
+        match &self {
+            UnifiedResult::ResultWarning(Err(e)) | UnifiedResult::ResultNoWarns(Err(e)) => f(e),
+            _ => (),
+        }
+        self
+    }
+
+    /// Same as [`UnifiedResult::into_result_with_warnings`], the counterpart of
+    /// [`UnifiedResult::uf_unwrap`] that leaves the warnings to the caller.
+    pub fn uf_unwrap_with_warnings(self) -> Result<OkWarning<T>, ErrorArrayItem> {
+        self.into_result_with_warnings()
+    }
+
+    /// Converts into a std `Result` keeping any warnings, nothing is displayed.
+    /// A result without warnings gets an empty warning array.
+    pub fn into_result_with_warnings(self) -> Result<OkWarning<T>, ErrorArrayItem> {
+        match self {
+            UnifiedResult::ResultWarning(r) => r,
+            UnifiedResult::ResultNoWarns(r) => r.map(OkWarning::new_none),
+        }
+    }
+
     /// Determines if the value in UnifiedResult is Ok()
     pub const fn is_ok(&self) -> bool {
         match &self {
@@ -520,57 +2591,416 @@ impl<T> UnifiedResult<T> {
             Err(e) => Some(e),
         }
     }
+
+    /// Maps the ok value with `f`, keeping the variant and any warnings untouched.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{
+    ///     OkWarning, UnifiedResult, WarningArrayItem, Warnings,
+    /// };
+    ///
+    /// let fetched: UnifiedResult<&str> = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+    ///     "42",
+    ///     WarningArrayItem::new(Warnings::OutdatedVersion),
+    /// )));
+    ///
+    /// let parsed: UnifiedResult<usize> = fetched.map(|body| body.len());
+    /// let (len, warnings) = parsed.into_result_with_warnings().unwrap().into_parts();
+    /// assert_eq!(len, 2);
+    /// assert!(warnings.contains_type(Warnings::OutdatedVersion));
+    /// ```
+    pub fn map<U, F>(self, f: F) -> UnifiedResult<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            UnifiedResult::ResultWarning(r) => UnifiedResult::ResultWarning(r.map(|d| d.map(f))),
+            UnifiedResult::ResultNoWarns(r) => UnifiedResult::ResultNoWarns(r.map(f)),
+        }
+    }
+
+    /// Maps the error with `f`, keeping the variant and any warnings untouched.
+    pub fn map_err<F>(self, f: F) -> UnifiedResult<T>
+    where
+        F: FnOnce(ErrorArrayItem) -> ErrorArrayItem,
+    {
+        match self {
+            UnifiedResult::ResultWarning(r) => UnifiedResult::ResultWarning(r.map_err(f)),
+            UnifiedResult::ResultNoWarns(r) => UnifiedResult::ResultNoWarns(r.map_err(f)),
+        }
+    }
+
+    /// Chains another fallible operation on the ok value.
+    ///
+    /// Warnings already collected are carried into the result of `f` and
+    /// merged ahead of any warnings `f` produces. They are neither displayed
+    /// nor cleared. If `f` fails the collected warnings are dropped along
+    /// with the success value, as an error carries no warnings.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::errors::{
+    ///     OkWarning, UnifiedResult as uf, WarningArrayItem, Warnings,
+    /// };
+    ///
+    /// fn open_config() -> uf<String> {
+    ///     uf::new_warn(Ok(OkWarning::new_from_item(
+    ///         String::from("port=8080"),
+    ///         WarningArrayItem::new(Warnings::OutdatedVersion),
+    ///     )))
+    /// }
+    ///
+    /// fn parse_config(raw: String) -> uf<u16> {
+    ///     uf::new(Ok(raw.trim_start_matches("port=").parse().unwrap()))
+    /// }
+    ///
+    /// fn validate_config(port: u16) -> uf<u16> {
+    ///     uf::new_warn(Ok(OkWarning::new_from_item(
+    ///         port,
+    ///         WarningArrayItem::new(Warnings::UnexpectedConfiguration),
+    ///     )))
+    /// }
+    ///
+    /// let result = open_config().and_then(parse_config).and_then(validate_config);
+    /// let (port, warnings) = result.into_result_with_warnings().unwrap().into_parts();
+    /// assert_eq!(port, 8080);
+    /// assert_eq!(warnings.len(), 2);
+    /// ```
+    pub fn and_then<U, F>(self, f: F) -> UnifiedResult<U>
+    where
+        F: FnOnce(T) -> UnifiedResult<U>,
+    {
+        match self {
+            UnifiedResult::ResultWarning(Ok(d)) => {
+                let mut warnings: WarningArray = d.warning;
+                match f(d.data) {
+                    UnifiedResult::ResultWarning(Ok(next)) => {
+                        warnings.append(next.warning);
+                        UnifiedResult::ResultWarning(Ok(OkWarning {
+                            data: next.data,
+                            warning: warnings,
+                        }))
+                    }
+                    UnifiedResult::ResultNoWarns(Ok(next)) => {
+                        UnifiedResult::ResultWarning(Ok(OkWarning {
+                            data: next,
+                            warning: warnings,
+                        }))
+                    }
+                    UnifiedResult::ResultWarning(Err(e)) | UnifiedResult::ResultNoWarns(Err(e)) => {
+                        UnifiedResult::ResultWarning(Err(e))
+                    }
+                }
+            }
+            UnifiedResult::ResultWarning(Err(e)) => UnifiedResult::ResultWarning(Err(e)),
+            UnifiedResult::ResultNoWarns(Ok(d)) => f(d),
+            UnifiedResult::ResultNoWarns(Err(e)) => UnifiedResult::ResultNoWarns(Err(e)),
+        }
+    }
+
+    /// Same as [`UnifiedResult::and_then`], named to read clearly next to
+    /// `Result::and_then` when chaining `uf` returning steps.
+    pub fn and_then_uf<U, F>(self, f: F) -> UnifiedResult<U>
+    where
+        F: FnOnce(T) -> UnifiedResult<U>,
+    {
+        self.and_then(f)
+    }
 }
 
-#[cfg(rust_comp_feature = "try_trait_v2")]
-// Implement FromResidual<Result<Infallible, UnifiedResult<_>>> for UnifiedResult
-impl<T> FromResidual<Result<Infallible, UnifiedResult<T>>> for UnifiedResult<T> {
-    fn from_residual(residual: Result<Infallible, UnifiedResult<T>>) -> Self {
-        match residual.unwrap_err() {
-            UnifiedResult::ResultWarning(_) => {
-                // Since Infallible can never be constructed, this code path is unreachable
-                unreachable!()
+/// Combines independent results into one.
+///
+/// All results are consumed, none short-circuit, so every failure is reported:
+///
+/// * If all succeed the values are returned in order. Warnings from every result are
+///   merged into one array and the result is `ResultWarning`, or `ResultNoWarns` if
+///   no result carried warnings.
+/// * If exactly one fails its error is returned unchanged.
+/// * If several fail the error keeps the kind of the first failure and its message lists
+///   every failure, `"2 of 3 operations failed: first; second"`, including each kind.
+///
+/// An error carries no warnings, so warnings collected alongside a failure are dropped.
+///
+/// ```rust
+/// use dusa_collection_utils::errors::{collect_results, ErrorArrayItem, Errors, UnifiedResult as uf};
+///
+/// let all: uf<Vec<u8>> = collect_results(vec![uf::new(Ok(1)), uf::new(Ok(2))]);
+/// assert_eq!(all.unwrap(), vec![1, 2]);
+///
+/// let failed: uf<Vec<u8>> = collect_results(vec![
+///     uf::new(Ok(1)),
+///     uf::new(Err(ErrorArrayItem::new(Errors::NotFound, "a.txt"))),
+/// ]);
+/// assert_eq!(failed.get_err().unwrap().err_mesg.as_str(), "a.txt");
+/// ```
+pub fn collect_results<T>(results: Vec<UnifiedResult<T>>) -> UnifiedResult<Vec<T>> {
+    let total: usize = results.len();
+    let mut values: Vec<T> = Vec::with_capacity(total);
+    let mut failures: Vec<ErrorArrayItem> = Vec::new();
+    let mut warnings: WarningArray = WarningArray::new_container();
+    let mut warned: bool = false;
+
+    for result in results {
+        match result {
+            UnifiedResult::ResultWarning(Ok(d)) => {
+                warned = true;
+                warnings.append(d.warning);
+                values.push(d.data);
             }
-            UnifiedResult::ResultNoWarns(_) => {
-                // Since Infallible can never be constructed, this code path is unreachable
-                unreachable!()
+            UnifiedResult::ResultNoWarns(Ok(d)) => values.push(d),
+            UnifiedResult::ResultWarning(Err(e)) | UnifiedResult::ResultNoWarns(Err(e)) => {
+                failures.push(e)
             }
         }
     }
+
+    match failures.len() {
+        0 if warned => UnifiedResult::new_warn(Ok(OkWarning {
+            data: values,
+            warning: warnings,
+        })),
+        0 => UnifiedResult::new(Ok(values)),
+        1 => UnifiedResult::new(Err(failures.remove(0))),
+        count => {
+            let messages: Vec<String> = failures
+                .iter()
+                .map(|err| err.format_with(DisplayStyle::Short))
+                .collect();
+            UnifiedResult::new(Err(ErrorArrayItem::new(
+                failures[0].err_type,
+                format!(
+                    "{} of {} operations failed: {}",
+                    count,
+                    total,
+                    messages.join("; ")
+                ),
+            )))
+        }
+    }
+}
+
+// Collects with the same merging rules as collect_results
+impl<T> FromIterator<UnifiedResult<T>> for UnifiedResult<Vec<T>> {
+    fn from_iter<I: IntoIterator<Item = UnifiedResult<T>>>(iter: I) -> Self {
+        collect_results(iter.into_iter().collect())
+    }
+}
+
+// Lets `?` on a `Result<_, E>` return early from a function returning `UnifiedResult`
+#[cfg(feature = "nightly-try")]
+impl<T, E> FromResidual<Result<Infallible, E>> for UnifiedResult<T>
+where
+    E: Into<ErrorArrayItem>,
+{
+    fn from_residual(residual: Result<Infallible, E>) -> Self {
+        match residual {
+            Err(e) => UnifiedResult::ResultNoWarns(Err(e.into())),
+            Ok(never) => match never {},
+        }
+    }
+}
+
+// `?` on a UnifiedResult yields an OkWarning holding the data and any warnings, nothing
+// is displayed, and returns errors early through the FromResidual impl above. Only
+// built with the `nightly-try` feature, stable code uses `ok_or_return!` instead.
+#[cfg(feature = "nightly-try")]
+impl<T> Try for UnifiedResult<T> {
+    type Output = OkWarning<T>;
+    type Residual = Result<Infallible, ErrorArrayItem>;
+
+    fn from_output(output: Self::Output) -> Self {
+        match output.warning.is_empty() {
+            true => UnifiedResult::ResultNoWarns(Ok(output.data)),
+            false => UnifiedResult::ResultWarning(Ok(output)),
+        }
+    }
+
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output> {
+        match self.into_result_with_warnings() {
+            Ok(data) => ControlFlow::Continue(data),
+            Err(e) => ControlFlow::Break(Err(e)),
+        }
+    }
+}
+
+/// Unwraps a `Result` or returns its error early as a [`UnifiedResult`].
+///
+/// This is the stable counterpart to using `?` on a `Result` inside a function
+/// returning `UnifiedResult`. The error only needs to convert into an
+/// [`ErrorArrayItem`]. Pass a `UnifiedResult` through
+/// [`UnifiedResult::into_result_with_warnings`] first to propagate it the same way
+/// while keeping its warnings.
+///
+/// ```rust
+/// use dusa_collection_utils::errors::{
+///     OkWarning, UnifiedResult as uf, WarningArrayItem, Warnings,
+/// };
+/// use dusa_collection_utils::ok_or_return;
+///
+/// fn parse(input: &str) -> uf<u32> {
+///     let value: u32 = ok_or_return!(input.parse::<u32>());
+///     uf::new_warn(Ok(OkWarning::new_from_item(
+///         value * 2,
+///         WarningArrayItem::new(Warnings::OutdatedVersion),
+///     )))
+/// }
+///
+/// fn parse_twice(input: &str) -> uf<u32> {
+///     let parsed: OkWarning<u32> = ok_or_return!(parse(input).into_result_with_warnings());
+///     uf::new_warn(Ok(parsed.map(|value| value * 2)))
+/// }
+///
+/// let (value, warnings) = parse_twice("21").into_result_with_warnings().unwrap().into_parts();
+/// assert_eq!(value, 84);
+/// assert!(warnings.contains_type(Warnings::OutdatedVersion));
+/// assert!(parse_twice("nope").is_err());
+/// ```
+#[macro_export]
+macro_rules! ok_or_return {
+    ($result:expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(err) => {
+                return $crate::errors::UnifiedResult::from($crate::errors::ErrorArrayItem::from(
+                    err,
+                ))
+            }
+        }
+    };
 }
 
 // Pretty display for WarningArrayItem
 impl fmt::Display for WarningArrayItem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.warn_mesg {
-            Some(d) => write!(f, "Warning: {:#?} - {}", self.warn_type, d),
-            None => write!(f, "Warning: {:#?}", self.warn_type),
+            Some(d) => write!(f, "Warning: {} - {}", self.warn_type, d)?,
+            None => write!(f, "Warning: {}", self.warn_type)?,
+        }
+        match self.count > 1 {
+            true => write!(f, " (x{})", self.count),
+            false => Ok(()),
+        }
+    }
+}
+
+// Serialized like the struct, with the kind's stable numeric code next to its name.
+// The code is ignored when deserializing, the name is authoritative.
+impl Serialize for ErrorArrayItem {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let fields: usize = if self.context.is_empty() { 4 } else { 5 };
+        let mut state = serializer.serialize_struct("ErrorArrayItem", fields)?;
+        state.serialize_field("err_type", &self.err_type)?;
+        state.serialize_field("code", &self.err_type.code())?;
+        state.serialize_field("err_mesg", &self.err_mesg)?;
+        if !self.context.is_empty() {
+            state.serialize_field("context", &self.context)?;
+        }
+        state.serialize_field("timestamp", &self.created_at)?;
+        state.end()
+    }
+}
+
+// Quotes a value for ErrorArrayItem::to_log_line
+fn quote_log_value(value: &str) -> String {
+    let mut quoted: String = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            _ => quoted.push(c),
         }
     }
+    quoted.push('"');
+    quoted
 }
 
-// Pretty display for ErrorArrayItem
+// `{}` follows the global DisplayStyle, `{:#}` writes a multi-line block for debugging
 impl fmt::Display for ErrorArrayItem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return write!(f, "{}", self.format_with(get_display_style()));
+        }
+
         write!(
             f,
-            "We encountered the following error: {:#?} - {}",
-            self.err_type, self.err_mesg
-        )
+            "kind: {:?}\ncode: {}\nmessage: {}",
+            self.err_type,
+            self.err_type.code(),
+            self.err_mesg
+        )?;
+        if !self.context.is_empty() {
+            write!(f, "\ncontext:")?;
+            for (key, value) in &self.context {
+                write!(f, "\n  {}: {}", key, value)?;
+            }
+        }
+        #[cfg(feature = "backtrace")]
+        if let Some(backtrace) = self.backtrace() {
+            write!(f, "\nbacktrace:\n{}", backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+// Conversion from ErrorArrayItem to a failed UnifiedResult
+impl<T> From<ErrorArrayItem> for UnifiedResult<T> {
+    fn from(value: ErrorArrayItem) -> Self {
+        UnifiedResult::ResultNoWarns(Err(value))
+    }
+}
+
+// Conversion from a std Result to a UnifiedResult without warnings
+impl<T> From<Result<T, ErrorArrayItem>> for UnifiedResult<T> {
+    fn from(value: Result<T, ErrorArrayItem>) -> Self {
+        UnifiedResult::ResultNoWarns(value)
+    }
+}
+
+// Conversion from a boxed dynamic error to ErrorArrayItem, keeping its source chain
+impl From<Box<dyn std::error::Error + Send + Sync>> for ErrorArrayItem {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        ErrorArrayItem::from_dyn(Errors::GeneralError, err.as_ref())
+    }
+}
+
+// Conversion from anyhow::Error to ErrorArrayItem, keeping its context chain
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for ErrorArrayItem {
+    fn from(err: anyhow::Error) -> Self {
+        ErrorArrayItem::from_dyn(Errors::GeneralError, err.as_ref())
+    }
+}
+
+// Maps the kinds callers commonly branch on, everything else stays InputOutput.
+// Before this a missing file and a permission failure were both InputOutput.
+fn io_error_kind(kind: io::ErrorKind) -> Errors {
+    match kind {
+        io::ErrorKind::NotFound => Errors::NotFound,
+        io::ErrorKind::PermissionDenied => Errors::PermissionDenied,
+        io::ErrorKind::TimedOut => Errors::Timeout,
+        io::ErrorKind::AlreadyExists => Errors::AlreadyExists,
+        _ => Errors::InputOutput,
     }
 }
 
 // Conversion from std::io::Error to ErrorArrayItem
 impl From<io::Error> for ErrorArrayItem {
     fn from(err: io::Error) -> Self {
-        ErrorArrayItem::new(Errors::InputOutput, err.to_string())
+        ErrorArrayItem::new(io_error_kind(err.kind()), err.to_string())
     }
 }
 
 // Conversion from &mut std::io::Error to ErrorArrayItem
 impl From<&mut io::Error> for ErrorArrayItem {
     fn from(err: &mut io::Error) -> Self {
-        ErrorArrayItem::new(Errors::InputOutput, err.to_string())
+        ErrorArrayItem::new(io_error_kind(err.kind()), err.to_string())
     }
 }
 
@@ -675,31 +3105,56 @@ impl TryFrom<&mut regex::Error> for ErrorArrayItem {
     }
 }
 
+// I/O failures stay InputOutput, anything the parser rejected is JsonReading.
+// serde_json's message already ends with the line and column when known.
+fn json_error_item(err: &serde_json::Error) -> ErrorArrayItem {
+    let kind: Errors = match err.classify() {
+        serde_json::error::Category::Io => Errors::InputOutput,
+        _ => Errors::JsonReading,
+    };
+    ErrorArrayItem::new(kind, err.to_string())
+}
+
+// serde_yaml's message does not always carry the position, append it when missing.
+fn yaml_error_item(err: &serde_yaml::Error) -> ErrorArrayItem {
+    let message: String = err.to_string();
+    let message: String = match err.location() {
+        Some(location) if !message.contains("line") => format!(
+            "{} at line {} column {}",
+            message,
+            location.line(),
+            location.column()
+        ),
+        _ => message,
+    };
+    ErrorArrayItem::new(Errors::ConfigParsing, message)
+}
+
 // Conversion from serde_json::Error to ErrorArrayItem
 impl From<serde_json::Error> for ErrorArrayItem {
     fn from(err: serde_json::Error) -> Self {
-        ErrorArrayItem::new(Errors::InputOutput, err.to_string())
+        json_error_item(&err)
     }
 }
 
 // Conversion from &mut serde_json::Error to ErrorArrayItem
 impl From<&mut serde_json::Error> for ErrorArrayItem {
     fn from(err: &mut serde_json::Error) -> Self {
-        ErrorArrayItem::new(Errors::InputOutput, err.to_string())
+        json_error_item(err)
     }
 }
 
 // Conversion from serde_yaml::Error to ErrorArrayItem
 impl From<serde_yaml::Error> for ErrorArrayItem {
     fn from(err: serde_yaml::Error) -> Self {
-        ErrorArrayItem::new(Errors::InputOutput, err.to_string())
+        yaml_error_item(&err)
     }
 }
 
 // Conversion from &mut serde_yaml::Error to ErrorArrayItem
 impl From<&mut serde_yaml::Error> for ErrorArrayItem {
     fn from(err: &mut serde_yaml::Error) -> Self {
-        ErrorArrayItem::new(Errors::InputOutput, err.to_string())
+        yaml_error_item(err)
     }
 }
 
@@ -815,6 +3270,68 @@ impl From<&mut ParseIntError> for ErrorArrayItem {
     }
 }
 
+// Conversion from ParseFloatError errors to ErrorArrayItem
+impl From<ParseFloatError> for ErrorArrayItem {
+    fn from(value: ParseFloatError) -> Self {
+        ErrorArrayItem::new(Errors::InvalidType, value.to_string())
+    }
+}
+
+// Conversion from &mut ParseFloatError errors to ErrorArrayItem
+impl From<&mut ParseFloatError> for ErrorArrayItem {
+    fn from(value: &mut ParseFloatError) -> Self {
+        ErrorArrayItem::new(Errors::InvalidType, value.to_string())
+    }
+}
+
+// Conversion from TryFromSliceError errors to ErrorArrayItem
+impl From<TryFromSliceError> for ErrorArrayItem {
+    fn from(value: TryFromSliceError) -> Self {
+        ErrorArrayItem::new(Errors::InvalidType, value.to_string())
+    }
+}
+
+// Conversion from &mut TryFromSliceError errors to ErrorArrayItem
+impl From<&mut TryFromSliceError> for ErrorArrayItem {
+    fn from(value: &mut TryFromSliceError) -> Self {
+        ErrorArrayItem::new(Errors::InvalidType, value.to_string())
+    }
+}
+
+// Conversion from CharTryFromError errors to ErrorArrayItem
+impl From<CharTryFromError> for ErrorArrayItem {
+    fn from(value: CharTryFromError) -> Self {
+        ErrorArrayItem::new(Errors::InvalidType, value.to_string())
+    }
+}
+
+// Conversion from &mut CharTryFromError errors to ErrorArrayItem
+impl From<&mut CharTryFromError> for ErrorArrayItem {
+    fn from(value: &mut CharTryFromError) -> Self {
+        ErrorArrayItem::new(Errors::InvalidType, value.to_string())
+    }
+}
+
+// Conversion from the OsString returned by a failed `OsString::into_string`
+impl From<OsString> for ErrorArrayItem {
+    fn from(value: OsString) -> Self {
+        ErrorArrayItem::new(
+            Errors::InvalidUtf8Data,
+            format!("not valid unicode: {}", value.to_string_lossy()),
+        )
+    }
+}
+
+// Conversion from the &mut OsString returned by a failed `OsString::into_string`
+impl From<&mut OsString> for ErrorArrayItem {
+    fn from(value: &mut OsString) -> Self {
+        ErrorArrayItem::new(
+            Errors::InvalidUtf8Data,
+            format!("not valid unicode: {}", value.to_string_lossy()),
+        )
+    }
+}
+
 #[allow(deprecated)]
 // Conversion from deprecated system Errors
 impl From<SystemError> for ErrorArrayItem {
@@ -861,6 +3378,25 @@ impl From<tokio::sync::TryLockError> for ErrorArrayItem {
         ErrorArrayItem::new(Errors::GeneralError, value.to_string())
     }
 }
+
+// Conversion from tokio::task::JoinError to ErrorArrayItem
+// A panicked task is a GeneralError, a cancelled task was lost rather than failed
+impl From<tokio::task::JoinError> for ErrorArrayItem {
+    fn from(value: tokio::task::JoinError) -> Self {
+        let kind: Errors = match value.is_cancelled() {
+            true => Errors::SupervisedChildLost,
+            false => Errors::GeneralError,
+        };
+        ErrorArrayItem::new(kind, value.to_string())
+    }
+}
+
+// Conversion from tokio::time::error::Elapsed to ErrorArrayItem
+impl From<tokio::time::error::Elapsed> for ErrorArrayItem {
+    fn from(value: tokio::time::error::Elapsed) -> Self {
+        ErrorArrayItem::new(Errors::Timeout, value.to_string())
+    }
+}
 // #[allow(deprecated)]
 // // Conversion from deprecated logging errors
 // impl From<LoggerError> for ErrorArrayItem {
@@ -0,0 +1,157 @@
+//! Per-run error counts persisted between runs.
+//!
+//! A process snapshots its error counts at shutdown with [`RunStats::snapshot`] or
+//! [`RunStats::snapshot_global`] and appends them to a history file as one JSON line.
+//! [`load_history`] reads the most recent runs back and [`trend`] tells whether a kind of
+//! error is becoming more or less frequent across them.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::OpenOptions,
+    io::Write,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{
+        global, ErrorArray, ErrorArrayItem, Errors, OkWarning, UnifiedResult as uf, WarningArray,
+        WarningArrayItem, Warnings,
+    },
+    functions::current_timestamp,
+    types::PathType,
+};
+
+/// The error counts of a single run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStats {
+    /// Unix timestamp (seconds) of when the counts were taken.
+    pub timestamp: u64,
+    /// Id of the process that recorded the run.
+    pub pid: u32,
+    /// Number of errors of each kind, kinds that did not occur are left out.
+    pub counts: BTreeMap<Errors, usize>,
+}
+
+/// Direction a kind of error is moving in across runs, see [`trend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trend {
+    /// The kind occurs more often in later runs.
+    Increasing,
+    /// The kind occurs less often in later runs.
+    Decreasing,
+    /// No change, or fewer than two runs to compare.
+    Steady,
+}
+
+impl RunStats {
+    /// Builds a record for the current process from per-kind counts.
+    pub fn from_counts(counts: HashMap<Errors, usize>) -> Self {
+        RunStats {
+            timestamp: current_timestamp(),
+            pid: std::process::id(),
+            counts: counts.into_iter().filter(|(_, count)| *count > 0).collect(),
+        }
+    }
+
+    /// Counts the errors currently held by `errors`, see [`ErrorArray::count_by_type`].
+    pub fn snapshot(errors: &ErrorArray) -> Self {
+        Self::from_counts(errors.count_by_type())
+    }
+
+    /// Counts the errors currently held by the [`global`] collector without draining it.
+    pub fn snapshot_global() -> Self {
+        Self::snapshot(global())
+    }
+
+    /// Number of errors of `kind` in this run.
+    pub fn count(&self, kind: Errors) -> usize {
+        self.counts.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Appends the record to `path` as a single JSON line, creating the file if needed.
+    ///
+    /// The line is written with one `write` on a file opened for appending, so records
+    /// from processes sharing a history file do not interleave.
+    pub fn append_to(&self, path: &PathType) -> Result<(), ErrorArrayItem> {
+        let mut line: String = serde_json::to_string(self).map_err(ErrorArrayItem::from)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(ErrorArrayItem::from)?;
+        file.write_all(line.as_bytes())
+            .map_err(ErrorArrayItem::from)
+    }
+}
+
+/// Reads up to the `last_n` most recent runs from `path`, oldest first.
+///
+/// A missing file is an empty history. Lines that do not parse are skipped and reported
+/// as warnings naming the line.
+pub fn load_history(path: &PathType, last_n: usize) -> uf<Vec<RunStats>> {
+    let content: String = match path.read_to_string() {
+        Ok(content) => content,
+        Err(_) if matches!(path.try_exists(), Ok(false)) => String::new(),
+        Err(err) => return uf::new(Err(err)),
+    };
+
+    let mut runs: VecDeque<RunStats> = VecDeque::with_capacity(last_n);
+    let mut warnings: WarningArray = WarningArray::new_container();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RunStats>(line) {
+            Ok(run) => {
+                if runs.len() == last_n {
+                    runs.pop_front();
+                }
+                if last_n > 0 {
+                    runs.push_back(run);
+                }
+            }
+            Err(err) => warnings.push(WarningArrayItem::new_details(
+                Warnings::UnexpectedBehavior,
+                format!("{} line {} is not a run record: {}", path, index + 1, err),
+            )),
+        }
+    }
+
+    let runs: Vec<RunStats> = runs.into();
+    match warnings.is_empty() {
+        true => uf::new(Ok(runs)),
+        false => uf::new_warn(Ok(OkWarning {
+            data: runs,
+            warning: warnings,
+        })),
+    }
+}
+
+/// Tells whether `kind` is becoming more or less frequent across `history`, oldest first.
+///
+/// The direction is the sign of the least squares slope of the counts, so a single
+/// outlier run does not decide it on its own.
+pub fn trend(history: &[RunStats], kind: Errors) -> Trend {
+    if history.len() < 2 {
+        return Trend::Steady;
+    }
+
+    let counts: Vec<f64> = history.iter().map(|run| run.count(kind) as f64).collect();
+    let mean_x: f64 = (counts.len() - 1) as f64 / 2.0;
+    let mean_y: f64 = counts.iter().sum::<f64>() / counts.len() as f64;
+    // Numerator of the slope, the denominator is always positive
+    let slope: f64 = counts
+        .iter()
+        .enumerate()
+        .map(|(x, y)| (x as f64 - mean_x) * (y - mean_y))
+        .sum();
+
+    match slope {
+        s if s > f64::EPSILON => Trend::Increasing,
+        s if s < -f64::EPSILON => Trend::Decreasing,
+        _ => Trend::Steady,
+    }
+}
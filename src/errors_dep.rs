@@ -23,10 +23,12 @@ pub enum SystemErrorType {
 // pretty display
 impl fmt::Display for SystemError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.details {
-            Some(d) => write!(f, "Logger Error: {} - {}", self.kind_description(), d),
-            None => write!(f, "Logger Error: {}", self.kind_description()),
-        }
+        let message: String = match &self.details {
+            Some(d) => format!("{} - {}", self.kind_description(), d),
+            None => self.kind_description(),
+        };
+        let item = crate::errors::ErrorArrayItem::new(crate::errors::Errors::DEPSYSTEM, message);
+        write!(f, "{}", item)
     }
 }
 
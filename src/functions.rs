@@ -1,10 +1,13 @@
-use crate::errors::{ErrorArrayItem, WarningArrayItem, Warnings};
+use crate::errors::{ErrorArrayItem, Errors, ResultExt, WarningArrayItem, Warnings};
+use crate::pathguard::PathGuard;
 use crate::stringy::Stringy;
-use crate::{errors, types};
+use crate::{errors, ok_or_return, types};
 use std::fs::OpenOptions;
-use std::io::{self, BufRead, BufReader, BufWriter, Read};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
 use std::os::unix::fs::{chown, MetadataExt};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
 use std::{
     fs::{self, remove_file, File},
     os::unix::prelude::PermissionsExt,
@@ -16,6 +19,7 @@ use flate2::bufread::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use nix::unistd::{Gid, Uid};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tar::{Archive, Builder};
 use types::{ClonePath, PathType};
@@ -141,6 +145,104 @@ where
     }
 }
 
+lazy_static::lazy_static! {
+    static ref DIGIT_SEPARATOR: RwLock<char> = RwLock::new(',');
+}
+
+/// Sets the separator used by [`group_digits`] and the helpers built on it. Defaults to `,`.
+pub fn set_digit_separator(separator: char) {
+    match DIGIT_SEPARATOR.write() {
+        Ok(mut current) => *current = separator,
+        Err(poisoned) => *poisoned.into_inner() = separator,
+    }
+}
+
+fn digit_separator() -> char {
+    match DIGIT_SEPARATOR.read() {
+        Ok(current) => *current,
+        Err(_) => ',',
+    }
+}
+
+/// Formats a number with its digits grouped in thousands, e.g. `1234567` becomes `1,234,567`.
+///
+/// # Arguments
+///
+/// * `n` - The number to format.
+///
+/// # Returns
+///
+/// Returns the grouped number using the separator set with [`set_digit_separator`].
+pub fn group_digits<N>(n: N) -> Stringy
+where
+    N: Into<u128>,
+{
+    let digits: String = n.into().to_string();
+    let separator: char = digit_separator();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+
+    Stringy::from(grouped)
+}
+
+/// Formats a count with the matching noun, e.g. `1 file` or `1,024 files`.
+///
+/// # Arguments
+///
+/// * `n` - The count.
+/// * `singular` - The noun used when `n` is exactly one.
+/// * `plural` - The noun used otherwise.
+///
+/// # Returns
+///
+/// Returns the grouped count followed by the noun.
+pub fn fmt_count<N>(n: N, singular: &str, plural: &str) -> Stringy
+where
+    N: Into<u128>,
+{
+    let n: u128 = n.into();
+    let noun: &str = if n == 1 { singular } else { plural };
+    Stringy::from(format!("{} {}", group_digits(n), noun))
+}
+
+/// Formats a throughput as a short per-second rate, e.g. `12.3k/s`.
+///
+/// # Arguments
+///
+/// * `count` - The number of items processed.
+/// * `duration` - How long processing took. A zero duration is reported as `0/s`.
+///
+/// # Returns
+///
+/// Returns the rate scaled to `k`, `M`, `G` or `T` with one decimal place.
+pub fn fmt_rate<N>(count: N, duration: Duration) -> Stringy
+where
+    N: Into<u128>,
+{
+    let seconds: f64 = duration.as_secs_f64();
+    if seconds == 0.0 {
+        return Stringy::from("0/s");
+    }
+
+    let mut rate: f64 = count.into() as f64 / seconds;
+    let mut unit: &str = "";
+    for next_unit in ["k", "M", "G", "T"] {
+        if rate < 1000.0 {
+            break;
+        }
+        rate /= 1000.0;
+        unit = next_unit;
+    }
+
+    Stringy::from(format!("{:.1}{}/s", rate, unit))
+}
+
 /// Creates a directory with the specified permissions.
 ///
 /// # Arguments
@@ -157,24 +259,9 @@ where
     S: Into<String> + Clone,
 {
     let permissions = fs::Permissions::from_mode(permissions);
-    let file_creation_result =
-        fs::create_dir(folder_name.clone().into()).map_err(|err| ErrorArrayItem::from(err));
-
-    match file_creation_result {
-        Ok(_) => {
-            let set_permission = fs::set_permissions(folder_name.into(), permissions)
-                .map_err(|err| ErrorArrayItem::from(err));
-            match set_permission {
-                Ok(_) => return uf::new(Ok(())),
-                Err(e) => {
-                    return uf::new(Err(e));
-                }
-            }
-        }
-        Err(e) => {
-            return uf::new(Err(e));
-        }
-    }
+    ok_or_return!(fs::create_dir(folder_name.clone().into()));
+    ok_or_return!(fs::set_permissions(folder_name.into(), permissions));
+    uf::new(Ok(()))
 }
 
 /// Recursively changes ownership of all files and directories in the given directory.
@@ -389,6 +476,31 @@ pub fn del_file(file: &PathType) -> uf<()> {
     }
 }
 
+/// Deletes a file after checking it against a [`PathGuard`].
+///
+/// Only the parent directory is resolved, see [`PathGuard::check_entry`], so when `file`
+/// is a symlink the link itself is removed and its target is left alone. Like the guard,
+/// this does not protect against directories being swapped between the check and the
+/// delete.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the file is deleted, with a warning if it did not exist.
+/// Returns a `PermissionDenied` error if the guard rejects the path.
+pub fn guarded_del_file(guard: &PathGuard, file: &PathType) -> uf<()> {
+    let checked: PathType = ok_or_return!(guard.check_entry(file));
+    match checked.symlink_metadata() {
+        Ok(_) => match remove_file(&checked) {
+            Ok(_) => uf::new(Ok(())),
+            Err(error) => uf::new(Err(ErrorArrayItem::from(error))),
+        },
+        Err(_) => uf::new_warn(Ok(OkWarning::new_from_item(
+            (),
+            WarningArrayItem::new_details(Warnings::Warning, String::from("The file didn't exist")),
+        ))),
+    }
+}
+
 /// Extracts the contents of a tar.gz file to a specified output folder.
 ///
 /// # Arguments
@@ -402,23 +514,14 @@ pub fn del_file(file: &PathType) -> uf<()> {
 /// Returns an error of type `ErrorArrayItem` if there is any issue encountered during the process.
 #[allow(deprecated)]
 pub fn untar(file_path: &PathType, output_folder: &PathType) -> uf<()> {
-    let tar_file: File = match open_file(file_path.clone_path(), false) {
-        Ok(d) => d,
-        Err(e) => {
-            return uf::new(Err(e));
-        }
-    };
+    let tar_file: File = ok_or_return!(open_file(file_path.clone_path(), false));
 
     let tar_reader: BufReader<File> = BufReader::new(tar_file);
     let tar: GzDecoder<BufReader<File>> = GzDecoder::new(tar_reader);
     let mut archive: Archive<GzDecoder<BufReader<File>>> = Archive::new(tar);
 
-    match archive.unpack(output_folder) {
-        Ok(_) => uf::new(Ok(())),
-        Err(e) => {
-            return uf::new(Err(ErrorArrayItem::from(e)));
-        }
-    }
+    ok_or_return!(archive.unpack(output_folder));
+    uf::new(Ok(()))
 }
 
 /// Creates a tar.gz file from the specified input folder and saves it to the given file path.
@@ -449,12 +552,12 @@ pub fn tar(input_folder: &PathType, output_file_path: &PathType) -> uf<()> {
     let encoder: GzEncoder<BufWriter<File>> = GzEncoder::new(output_writer, Compression::default());
     let mut tar_builder: Builder<GzEncoder<BufWriter<File>>> = Builder::new(encoder);
 
-    match tar_builder.append_dir_all(".", input_folder.clone_path()) {
-        Ok(_) => uf::new(Ok(())),
-        Err(e) => {
-            return uf::new(Err(ErrorArrayItem::from(e)));
-        }
-    }
+    let context: String = format!("archiving {}", input_folder);
+    uf::new(
+        tar_builder
+            .append_dir_all(".", input_folder.clone_path())
+            .wrap_err(context),
+    )
 }
 
 /// Opens a file.
@@ -467,7 +570,7 @@ pub fn tar(input_folder: &PathType, output_file_path: &PathType) -> uf<()> {
 /// Returns `Ok(file)` if the file exists and can be opened.
 /// Returns an error of type `ErrorArrayItem` if there is any issue encountered during the process.
 pub fn open_file(file: PathType, create: bool) -> Result<File, ErrorArrayItem> {
-    let file_path = file.canonicalize().map_err(|err| ErrorArrayItem::from(err));
+    let file_path = file.canonicalize();
 
     let file_result = OpenOptions::new()
         .read(true) // Open file with read
@@ -568,6 +671,165 @@ pub fn set_file_permission(socket_path: PathType, permissions: u32) -> uf<()> {
     uf::new(Ok(()))
 }
 
+/// Controls how aggressively write helpers flush data to disk.
+///
+/// * `Always` - Every written file and the directory entry that points at it are synced
+///   before returning. Survives power loss: after a crash either the old or the new
+///   content is on disk.
+/// * `OnClose` - File data is synced once when the file is closed, the directory entry
+///   is not. A crash shortly after returning may roll the rename back to the old file,
+///   but never leaves a torn file.
+/// * `Never` - Nothing is synced and the kernel flushes whenever it likes. Fastest, a
+///   crash may lose the write entirely or leave an empty file behind the rename.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncPolicy {
+    /// Sync file data and the parent directory.
+    Always,
+    /// Sync file data when the file is closed.
+    OnClose,
+    /// Never sync.
+    Never,
+}
+
+lazy_static::lazy_static! {
+    static ref SYNC_POLICY_OVERRIDE: RwLock<Option<SyncPolicy>> = RwLock::new(None);
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of sync calls made on this thread, used by the tests to observe the policy.
+    pub(crate) static SYNC_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Overrides the sync policy used by every write helper. `None` restores each
+/// helper's own default.
+pub fn set_default_sync_policy(policy: Option<SyncPolicy>) {
+    match SYNC_POLICY_OVERRIDE.write() {
+        Ok(mut current) => *current = policy,
+        Err(poisoned) => *poisoned.into_inner() = policy,
+    }
+}
+
+/// Returns the global override if one is set, otherwise `default`.
+pub fn default_sync_policy(default: SyncPolicy) -> SyncPolicy {
+    match SYNC_POLICY_OVERRIDE.read() {
+        Ok(current) => current.unwrap_or(default),
+        Err(_) => default,
+    }
+}
+
+fn sync_file(file: &File) -> io::Result<()> {
+    #[cfg(test)]
+    SYNC_CALLS.with(|calls| calls.set(calls.get() + 1));
+    file.sync_all()
+}
+
+/// Replaces the contents of a file without disturbing readers that already have it open.
+///
+/// The data is written to a temporary file next to `path` and renamed over it, so existing
+/// handles keep reading the old inode while new opens see the new content. When `keep_backup`
+/// is set the current file is hard linked to `<name>.prev` before the swap. The temporary file
+/// lives in the same directory as the target, so the rename never crosses filesystems.
+/// Uses [`SyncPolicy::Always`] unless overridden with [`set_default_sync_policy`].
+///
+/// # Arguments
+///
+/// * `path` - The file to replace. It does not need to exist yet.
+/// * `data` - The new contents of the file.
+/// * `keep_backup` - Whether to retain the previous version as `<name>.prev`.
+///
+/// # Returns
+///
+/// Returns `Ok(Some(backup))` with the backup path if one was made, `Ok(None)` otherwise.
+/// Returns an error of type `ErrorArrayItem` if there is any issue encountered during the process.
+pub fn write_and_swap(path: &PathType, data: &[u8], keep_backup: bool) -> uf<Option<PathType>> {
+    let policy = default_sync_policy(SyncPolicy::Always);
+    write_and_swap_with_policy(path, data, keep_backup, policy)
+}
+
+/// Same as [`write_and_swap`] with an explicit [`SyncPolicy`].
+pub fn write_and_swap_with_policy(
+    path: &PathType,
+    data: &[u8],
+    keep_backup: bool,
+    policy: SyncPolicy,
+) -> uf<Option<PathType>> {
+    let file_name = match path.file_name() {
+        Some(name) => name.to_string(),
+        None => {
+            return uf::new(Err(ErrorArrayItem::new(
+                Errors::InvalidFile,
+                format!("{} does not name a file", path),
+            )))
+        }
+    };
+
+    let parent: PathBuf = match path.parent() {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from("."),
+    };
+
+    let suffix = match generate_random_string(8).uf_unwrap() {
+        Ok(d) => d,
+        Err(e) => return uf::new(Err(e)),
+    };
+    let temp_path: PathBuf = parent.join(format!(".{}.{}.tmp", file_name, suffix));
+
+    let result = swap_into_place(
+        path,
+        &parent,
+        &file_name,
+        &temp_path,
+        data,
+        keep_backup,
+        policy,
+    );
+    if result.is_err() && temp_path.exists() {
+        let _ = remove_file(&temp_path);
+    }
+
+    uf::new(result)
+}
+
+fn swap_into_place(
+    path: &PathType,
+    parent: &Path,
+    file_name: &str,
+    temp_path: &Path,
+    data: &[u8],
+    keep_backup: bool,
+    policy: SyncPolicy,
+) -> Result<Option<PathType>, ErrorArrayItem> {
+    let mut temp_file: File = File::create_new(temp_path)?;
+    temp_file.write_all(data)?;
+    if policy != SyncPolicy::Never {
+        sync_file(&temp_file)?;
+    }
+    drop(temp_file);
+
+    let backup: Option<PathType> = match keep_backup && path.exists() {
+        true => {
+            // Link under a temporary name first so a failed link leaves the old backup intact
+            let backup_path: PathBuf = parent.join(format!("{}.prev", file_name));
+            let staged_path: PathBuf = temp_path.with_extension("prev");
+            fs::hard_link(path, &staged_path)?;
+            if let Err(e) = fs::rename(&staged_path, &backup_path) {
+                let _ = remove_file(&staged_path);
+                return Err(e.into());
+            }
+            Some(PathType::PathBuf(backup_path))
+        }
+        false => None,
+    };
+
+    fs::rename(temp_path, path)?;
+    if policy == SyncPolicy::Always {
+        sync_file(&File::open(parent)?)?;
+    }
+
+    Ok(backup)
+}
+
 /// Retrieves the current Unix timestamp in seconds.
 pub fn current_timestamp() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -578,7 +840,7 @@ pub fn current_timestamp() -> u64 {
     since_the_epoch.as_secs()
 }
 
-#[cfg(rust_comp_feature = "try_trait_v2")]
+#[cfg(feature = "nightly-try")]
 mod tests {
     #[test]
     fn try_trait() {
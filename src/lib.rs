@@ -1,12 +1,16 @@
 // #![feature(try_trait_v2)]
-#![cfg_attr(rust_comp_feature = "try_trait_v2", feature(try_trait_v2))]
+#![cfg_attr(feature = "nightly-try", feature(try_trait_v2))]
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub mod errors;
 #[deprecated(since = "0.1.0", note = "please use `errors` instead")]
 pub mod errors_dep;
 pub mod functions;
 pub mod log;
+pub mod negcache;
+pub mod pathguard;
+pub mod registry;
 pub mod rwarc;
+pub mod selfcheck;
 pub mod stringy;
 pub mod types;
 pub mod version;
@@ -15,8 +19,18 @@ pub mod version;
 pub mod errors_test;
 #[path = "tests/functions.rs"]
 pub mod function_test;
+#[path = "tests/negcache.rs"]
+pub mod negcache_test;
+#[path = "tests/pathguard.rs"]
+pub mod pathguard_test;
+#[path = "tests/registry.rs"]
+pub mod registry_test;
 #[path = "tests/rwarc.rs"]
 pub mod rwarc_test;
+#[path = "tests/selfcheck.rs"]
+pub mod selfcheck_test;
+#[path = "tests/stats.rs"]
+pub mod stats_test;
 #[path = "tests/stringy.rs"]
 pub mod stringy_test;
 #[path = "tests/pathtype.rs"]
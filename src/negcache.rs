@@ -0,0 +1,170 @@
+//! Time-limited cache of failing keys.
+//!
+//! Retry loops that keep hitting a broken path or endpoint can consult a [`NegativeCache`]
+//! before doing expensive work. A key is suppressed for `ttl` after its last recorded failure,
+//! a success forgets it immediately. The cache holds at most `capacity` keys, when full the
+//! least recently used key is evicted. Both recording a failure and a suppressed lookup
+//! count as a use.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::ErrorArrayItem, stringy::Stringy};
+
+/// Default number of keys a [`NegativeCache`] tracks.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone)]
+struct NegativeEntry {
+    last_error: ErrorArrayItem,
+    failures: u64,
+    expires: Instant,
+    touched: Instant,
+}
+
+/// A currently suppressed key, as reported by [`NegativeCache::snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuppressedEntry {
+    /// The failing key.
+    pub key: Stringy,
+    /// The most recent failure recorded for the key.
+    pub last_error: ErrorArrayItem,
+    /// Failures recorded since the key was last cleared or expired.
+    pub failures: u64,
+    /// Seconds left until the key is attempted again, rounded up.
+    pub remaining_secs: u64,
+}
+
+/// Negative cache of failing keys with expiry and a capacity cap.
+#[derive(Debug)]
+pub struct NegativeCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<Stringy, NegativeEntry>>,
+}
+
+impl NegativeCache {
+    /// Creates a cache suppressing keys for `ttl` and holding at most [`DEFAULT_CAPACITY`] keys.
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_CAPACITY)
+    }
+
+    /// Creates a cache suppressing keys for `ttl` and holding at most `capacity` keys.
+    /// A capacity of zero is treated as one.
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> Self {
+        NegativeCache {
+            ttl,
+            capacity: capacity.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Entries are only ever replaced whole, so a poisoned lock is recovered
+    fn lock(&self) -> MutexGuard<'_, HashMap<Stringy, NegativeEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Panics while holding the lock, leaving it poisoned.
+    #[cfg(test)]
+    pub(crate) fn poison(&self) {
+        let _entries = self.lock();
+        panic!("poisoning the negative cache");
+    }
+
+    /// Returns `false` while `key` has an unexpired failure recorded.
+    pub fn should_attempt(&self, key: &Stringy) -> bool {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        match entries.get_mut(key) {
+            Some(entry) if entry.expires > now => {
+                entry.touched = now;
+                false
+            }
+            Some(_) => {
+                entries.remove(key);
+                true
+            }
+            None => true,
+        }
+    }
+
+    /// Records a failure for `key`, suppressing it for another `ttl`.
+    pub fn record_failure<K: Into<Stringy>>(&self, key: K, error: &ErrorArrayItem) {
+        let key: Stringy = key.into();
+        let now = Instant::now();
+        let mut entries = self.lock();
+
+        let failures = match entries.get(&key) {
+            Some(entry) if entry.expires > now => entry.failures + 1,
+            _ => 1,
+        };
+
+        if !entries.contains_key(&key) && entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.touched)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            key,
+            NegativeEntry {
+                last_error: error.clone(),
+                failures,
+                expires: now + self.ttl,
+                touched: now,
+            },
+        );
+    }
+
+    /// Forgets any failure recorded for `key`.
+    pub fn record_success(&self, key: &Stringy) {
+        let mut entries = self.lock();
+        entries.remove(key);
+    }
+
+    /// Number of keys tracked, including expired ones not yet pruned.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no keys are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the currently suppressed keys, soonest to expire first.
+    /// Expired keys are pruned.
+    pub fn snapshot(&self) -> Vec<SuppressedEntry> {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        entries.retain(|_, entry| entry.expires > now);
+
+        let mut suppressed: Vec<(Instant, SuppressedEntry)> = entries
+            .iter()
+            .map(|(key, entry)| {
+                let remaining = entry.expires - now;
+                let remaining_secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                (
+                    entry.expires,
+                    SuppressedEntry {
+                        key: key.clone(),
+                        last_error: entry.last_error.clone(),
+                        failures: entry.failures,
+                        remaining_secs,
+                    },
+                )
+            })
+            .collect();
+        suppressed.sort_by_key(|(expires, _)| *expires);
+        suppressed.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
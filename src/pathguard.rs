@@ -0,0 +1,204 @@
+//! Restricts user supplied paths to a set of allowed roots.
+//!
+//! A [`PathGuard`] resolves a path the way the kernel would, one component at a time,
+//! following symlinks as it finds them. Targets that do not exist yet are resolved as far
+//! as they exist and the rest is taken literally. The resolved path must then sit under one
+//! of the allowed roots and none of its components below that root may match a denied
+//! pattern.
+
+use std::{
+    collections::VecDeque,
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    errors::{ErrorArrayItem, Errors},
+    stringy::Stringy,
+    types::PathType,
+};
+
+/// Symlinks followed while resolving a single path before giving up, matches Linux `MAXSYMLINKS`.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// Allow list of roots plus deny list of name patterns for file operations.
+#[derive(Debug, Clone)]
+pub struct PathGuard {
+    allowed_roots: Vec<PathBuf>,
+    denied_patterns: Vec<Stringy>,
+}
+
+impl PathGuard {
+    /// Creates a guard allowing paths under `allowed_roots`.
+    ///
+    /// `denied_patterns` are matched against every component of the resolved path below its
+    /// allowed root, never the root's own. `*` matches any run of characters and `?` a
+    /// single one, so `*.key` or `.ssh` work as expected. Roots are resolved once here, a root that cannot be resolved allows nothing.
+    pub fn new(allowed_roots: Vec<PathType>, denied_patterns: Vec<Stringy>) -> Self {
+        let allowed_roots: Vec<PathBuf> = allowed_roots
+            .iter()
+            .filter_map(|root| resolve(root).ok())
+            .collect();
+
+        PathGuard {
+            allowed_roots,
+            denied_patterns,
+        }
+    }
+
+    /// Resolves `p` and checks it against the guard's rules.
+    ///
+    /// Returns the resolved path, which callers should use instead of `p`. Violations are
+    /// reported as `PermissionDenied` naming the rule that failed. The check is not atomic
+    /// with whatever the caller does next, so directories writable by untrusted users can
+    /// still be swapped for a symlink in between.
+    pub fn check(&self, p: &PathType) -> Result<PathType, ErrorArrayItem> {
+        let resolved: PathBuf = resolve(p)?;
+        self.check_resolved(p, resolved)
+    }
+
+    /// Like [`check`](Self::check) but leaves the final component of `p` unresolved.
+    ///
+    /// Meant for operations on the directory entry itself, such as deleting it, so an
+    /// in-root symlink names the link and not whatever it points to. Paths without a final
+    /// name, like `/` or one ending in `..`, are resolved in full.
+    pub fn check_entry(&self, p: &PathType) -> Result<PathType, ErrorArrayItem> {
+        let path: &Path = p.as_ref();
+        let (parent, name) = match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => (parent, name),
+            _ => return self.check(p),
+        };
+
+        let resolved: PathBuf = resolve(parent)?.join(name);
+        self.check_resolved(p, resolved)
+    }
+
+    fn check_resolved(&self, p: &PathType, resolved: PathBuf) -> Result<PathType, ErrorArrayItem> {
+        // The deepest matching root wins, so an explicitly allowed nested root is not
+        // held to the patterns of the names above it
+        let root: &PathBuf = match self
+            .allowed_roots
+            .iter()
+            .filter(|root| resolved.starts_with(root))
+            .max_by_key(|root| root.components().count())
+        {
+            Some(root) => root,
+            None => {
+                return Err(ErrorArrayItem::new(
+                    Errors::PermissionDenied,
+                    format!(
+                        "{} resolves to {}, which is outside the allowed roots",
+                        p,
+                        resolved.display()
+                    ),
+                ))
+            }
+        };
+
+        let below_root: &Path = resolved.strip_prefix(root).unwrap_or(&resolved);
+        for component in below_root.components() {
+            if let Component::Normal(name) = component {
+                let name = name.to_string_lossy();
+                if let Some(pattern) = self
+                    .denied_patterns
+                    .iter()
+                    .find(|pattern| wildcard_match(pattern, &name))
+                {
+                    return Err(ErrorArrayItem::new(
+                        Errors::PermissionDenied,
+                        format!("{} is denied by pattern '{}'", p, pattern),
+                    ));
+                }
+            }
+        }
+
+        Ok(PathType::PathBuf(resolved))
+    }
+}
+
+/// Resolves `path` to an absolute path with every existing symlink followed.
+fn resolve(path: &Path) -> Result<PathBuf, ErrorArrayItem> {
+    let absolute: PathBuf = match path.is_absolute() {
+        true => path.to_path_buf(),
+        false => env::current_dir().map_err(ErrorArrayItem::from)?.join(path),
+    };
+
+    let mut resolved: PathBuf = PathBuf::from("/");
+    let mut pending: VecDeque<OsString> = components(&absolute);
+    let mut hops: usize = 0;
+
+    while let Some(component) = pending.pop_front() {
+        if component == OsStr::new("..") {
+            resolved.pop();
+            continue;
+        }
+
+        let candidate: PathBuf = resolved.join(&component);
+        match fs::symlink_metadata(&candidate) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                hops += 1;
+                if hops > MAX_SYMLINK_HOPS {
+                    return Err(ErrorArrayItem::new(
+                        Errors::PermissionDenied,
+                        format!("Too many levels of symbolic links in {}", path.display()),
+                    ));
+                }
+
+                let target: PathBuf = fs::read_link(&candidate).map_err(ErrorArrayItem::from)?;
+                if target.is_absolute() {
+                    resolved = PathBuf::from("/");
+                }
+                let mut expanded: VecDeque<OsString> = components(&target);
+                expanded.append(&mut pending);
+                pending = expanded;
+            }
+            _ => resolved = candidate,
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Splits a path into its `Normal` and `..` components.
+fn components(path: &Path) -> VecDeque<OsString> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_os_string()),
+            Component::ParentDir => Some(OsString::from("..")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Matches `name` against a pattern where `*` is any run of characters and `?` is one.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,298 @@
+//! Opt-in registry of named synchronization primitives.
+//!
+//! Primitives created through a named constructor such as [`LockWithTimeout::new_named`]
+//! are recorded here once, at construction, so a wedged service can be inspected from a
+//! debug endpoint with [`snapshot`]. The registry only holds weak references, it never keeps
+//! a primitive alive and entries whose primitive was dropped are skipped and pruned.
+//!
+//! Lock state is read from counters the lock keeps up to date as tracked guards are taken
+//! and released, taking a snapshot never touches the lock itself. Registration is a single
+//! lock-free push, the snapshot moves new records into its own list and prunes it there.
+//! So that a process which never takes a snapshot does not pile up records, registration
+//! does the same once a few hundred records are waiting, unless a snapshot holds the list
+//! at that moment.
+//!
+//! [`LockWithTimeout::new_named`]: crate::rwarc::LockWithTimeout::new_named
+
+use std::{
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, TryLockError, Weak,
+    },
+    time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{ErrorArrayItem, Errors, ResultExt},
+    stringy::Stringy,
+};
+
+lazy_static::lazy_static! {
+    static ref REGISTRY: Mutex<Vec<RegistryRecord>> = Mutex::new(Vec::new());
+}
+
+// Records registered since the last snapshot, newest first
+static PENDING: AtomicPtr<PendingRecord> = AtomicPtr::new(ptr::null_mut());
+
+// Records on the pending list. Raised before a record is published and lowered after it
+// is detached, so it never drops below the real length.
+static PENDING_LEN: AtomicUsize = AtomicUsize::new(0);
+
+// Number of pending records at which register moves them into the snapshot list itself,
+// pruning dropped primitives as snapshot does
+pub(crate) const PENDING_LIMIT: usize = 256;
+
+/// The kind of primitive a registry entry describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegistryKind {
+    /// A [`crate::rwarc::LockWithTimeout`].
+    LockWithTimeout,
+}
+
+/// The observed state of a lock at the time of the snapshot.
+///
+/// Only guards from [`LockWithTimeout::try_read_tracked`] and
+/// [`LockWithTimeout::try_write_tracked`] are seen, plain guards are counted in the stats
+/// but not here.
+///
+/// [`LockWithTimeout::try_read_tracked`]: crate::rwarc::LockWithTimeout::try_read_tracked
+/// [`LockWithTimeout::try_write_tracked`]: crate::rwarc::LockWithTimeout::try_write_tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockState {
+    /// Nobody holds the lock.
+    Unlocked,
+    /// One or more readers hold the lock.
+    ReadLocked,
+    /// A writer holds the lock.
+    WriteLocked,
+}
+
+/// Contention counters shared between a lock and its registry entry.
+#[derive(Debug, Default)]
+pub struct LockStats {
+    reads: AtomicU64,
+    writes: AtomicU64,
+    timeouts: AtomicU64,
+    readers: AtomicU64,
+    writer: AtomicBool,
+    pending_writers: AtomicU64,
+}
+
+impl LockStats {
+    pub(crate) fn record_read(&self) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_write(&self) {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn hold_read(&self) {
+        self.readers.fetch_add(1, Ordering::Release);
+    }
+
+    pub(crate) fn release_read(&self) {
+        self.readers.fetch_sub(1, Ordering::Release);
+    }
+
+    pub(crate) fn hold_write(&self) {
+        self.writer.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn release_write(&self) {
+        self.writer.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn writer_waiting(&self) {
+        self.pending_writers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn writer_done_waiting(&self) {
+        self.pending_writers.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Number of writers currently waiting for the lock.
+    pub fn pending_writers(&self) -> u64 {
+        self.pending_writers.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns a copy of the current counters.
+    pub fn snapshot(&self) -> LockStatsSnapshot {
+        LockStatsSnapshot {
+            reads: self.reads.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the state of the lock as tracked by the tracked guards it handed out.
+    pub fn state(&self) -> LockState {
+        if self.writer.load(Ordering::Acquire) {
+            LockState::WriteLocked
+        } else if self.readers.load(Ordering::Acquire) > 0 {
+            LockState::ReadLocked
+        } else {
+            LockState::Unlocked
+        }
+    }
+}
+
+/// A point in time copy of [`LockStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LockStatsSnapshot {
+    /// Number of read locks acquired.
+    pub reads: u64,
+    /// Number of write locks acquired.
+    pub writes: u64,
+    /// Number of acquisitions that gave up after timing out.
+    pub timeouts: u64,
+}
+
+/// Describes one live primitive in the registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// Name given at construction.
+    pub name: Stringy,
+    /// Kind of primitive.
+    pub kind: RegistryKind,
+    /// State of the lock when the snapshot was taken.
+    pub state: LockState,
+    /// Writers waiting for the lock when the snapshot was taken.
+    pub pending_writers: u64,
+    /// Contention counters.
+    pub stats: LockStatsSnapshot,
+    /// Seconds since the primitive was created.
+    pub age_secs: u64,
+}
+
+// The stats are shared by every clone of a primitive, so the weak reference dies with
+// the last clone
+struct RegistryRecord {
+    name: Stringy,
+    kind: RegistryKind,
+    created: Instant,
+    stats: Weak<LockStats>,
+}
+
+struct PendingRecord {
+    record: RegistryRecord,
+    next: *mut PendingRecord,
+}
+
+/// Records a primitive in the registry. Called once from named constructors, it never
+/// blocks: the record is pushed onto a lock-free list that [`snapshot`] drains. Past
+/// `PENDING_LIMIT` pending records the list is drained here too, if that can be done
+/// without waiting.
+pub(crate) fn register(name: Stringy, kind: RegistryKind, stats: &Arc<LockStats>) {
+    let node: *mut PendingRecord = Box::into_raw(Box::new(PendingRecord {
+        record: RegistryRecord {
+            name,
+            kind,
+            created: Instant::now(),
+            stats: Arc::downgrade(stats),
+        },
+        next: ptr::null_mut(),
+    }));
+
+    let pending: usize = PENDING_LEN.fetch_add(1, Ordering::Relaxed) + 1;
+    let mut head: *mut PendingRecord = PENDING.load(Ordering::Relaxed);
+    loop {
+        // SAFETY: the node is only reachable from this thread until the exchange publishes it
+        unsafe { (*node).next = head };
+        match PENDING.compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed) {
+            Ok(_) => break,
+            Err(current) => head = current,
+        }
+    }
+
+    if pending >= PENDING_LIMIT {
+        // A snapshot holding the list drains the pending records itself
+        let registry: Option<MutexGuard<'_, Vec<RegistryRecord>>> = match REGISTRY.try_lock() {
+            Ok(registry) => Some(registry),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        if let Some(mut registry) = registry {
+            absorb_pending(&mut registry);
+        }
+    }
+}
+
+// Detaches every pending record, oldest first. Nodes are only ever removed by swapping out
+// the whole list, so there is no ABA problem.
+fn take_pending() -> Vec<RegistryRecord> {
+    let mut node: *mut PendingRecord = PENDING.swap(ptr::null_mut(), Ordering::Acquire);
+    let mut records: Vec<RegistryRecord> = Vec::new();
+    while !node.is_null() {
+        // SAFETY: the swap above made this thread the sole owner of the detached list, and
+        // every node in it came from Box::into_raw in register
+        let pending: Box<PendingRecord> = unsafe { Box::from_raw(node) };
+        node = pending.next;
+        records.push(pending.record);
+    }
+    PENDING_LEN.fetch_sub(records.len(), Ordering::Relaxed);
+    records.reverse();
+    records
+}
+
+// Moves the pending records into the snapshot list and prunes dropped primitives from it
+fn absorb_pending(registry: &mut Vec<RegistryRecord>) {
+    registry.extend(take_pending());
+    registry.retain(|record| record.stats.strong_count() > 0);
+}
+
+/// Number of records registered but not yet moved into the snapshot list.
+#[cfg(test)]
+pub(crate) fn pending_len() -> usize {
+    PENDING_LEN.load(Ordering::Relaxed)
+}
+
+/// Names of every record the snapshot list holds, including dropped ones not yet pruned.
+#[cfg(test)]
+pub(crate) fn recorded_names() -> Vec<Stringy> {
+    match REGISTRY.lock() {
+        Ok(registry) => registry.iter().map(|record| record.name.clone()).collect(),
+        Err(poisoned) => poisoned
+            .into_inner()
+            .iter()
+            .map(|record| record.name.clone())
+            .collect(),
+    }
+}
+
+/// Returns an entry for every live registered primitive, pruning dropped ones.
+pub fn snapshot() -> Vec<RegistryEntry> {
+    let mut registry = match REGISTRY.lock() {
+        Ok(registry) => registry,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    absorb_pending(&mut registry);
+
+    registry
+        .iter()
+        .filter_map(|record| {
+            let stats = record.stats.upgrade()?;
+            Some(RegistryEntry {
+                name: record.name.clone(),
+                kind: record.kind,
+                state: stats.state(),
+                pending_writers: stats.pending_writers(),
+                stats: stats.snapshot(),
+                age_secs: record.created.elapsed().as_secs(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the current snapshot serialized as a JSON array.
+pub fn snapshot_json() -> Result<String, ErrorArrayItem> {
+    serde_json::to_string(&snapshot()).err_kind(Errors::JsonCreation)
+}
@@ -1,15 +1,105 @@
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::time::{self, timeout};
 
 use crate::errors::{ErrorArrayItem, Errors};
+use crate::registry::{self, LockStats, RegistryKind};
+use crate::stringy::Stringy;
+
+/// A read guard from [`LockWithTimeout::try_read_tracked`]. The lock is released when it
+/// is dropped, and a named lock's [`registry`] entry reports it as read locked until then.
+pub struct LockReadGuard<'a, T> {
+    guard: RwLockReadGuard<'a, T>,
+    stats: Option<&'a LockStats>,
+}
+
+impl<T> Deref for LockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> Drop for LockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(stats) = self.stats {
+            stats.release_read();
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.guard, f)
+    }
+}
+
+/// A write guard from [`LockWithTimeout::try_write_tracked`]. The lock is released when it
+/// is dropped, and a named lock's [`registry`] entry reports it as write locked until then.
+pub struct LockWriteGuard<'a, T> {
+    guard: RwLockWriteGuard<'a, T>,
+    stats: Option<&'a LockStats>,
+}
+
+impl<T> Deref for LockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for LockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for LockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(stats) = self.stats {
+            stats.release_write();
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.guard, f)
+    }
+}
+
+// Counts a writer as pending for as long as it waits, including when the wait is cancelled
+struct PendingWriter<'a>(Option<&'a LockStats>);
+
+impl<'a> PendingWriter<'a> {
+    fn new(stats: Option<&'a LockStats>) -> Self {
+        if let Some(stats) = stats {
+            stats.writer_waiting();
+        }
+        PendingWriter(stats)
+    }
+}
+
+impl Drop for PendingWriter<'_> {
+    fn drop(&mut self) {
+        if let Some(stats) = self.0 {
+            stats.writer_done_waiting();
+        }
+    }
+}
 
 /// A struct that encapsulates an `Arc<RwLock<T>>` and provides methods
 /// to acquire read and write locks with a timeout.
 #[derive(Debug, Clone)]
 pub struct LockWithTimeout<T> {
     state: Arc<RwLock<T>>,
+    stats: Option<Arc<LockStats>>,
 }
 
 impl<T> LockWithTimeout<T> {
@@ -25,9 +115,38 @@ impl<T> LockWithTimeout<T> {
     pub fn new(state: T) -> Self {
         Self {
             state: Arc::new(RwLock::new(state)),
+            stats: None,
         }
     }
 
+    /// Creates a new `LockWithTimeout` and records it in the [`registry`] under `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name reported by [`registry::snapshot`].
+    /// * `state` - The initial state to be wrapped by the `RwLock`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `LockWithTimeout`.
+    pub fn new_named<N>(name: N, state: T) -> Self
+    where
+        N: Into<String>,
+    {
+        let stats: Arc<LockStats> = Arc::new(LockStats::default());
+        registry::register(Stringy::from(name), RegistryKind::LockWithTimeout, &stats);
+        Self {
+            state: Arc::new(RwLock::new(state)),
+            stats: Some(stats),
+        }
+    }
+
+    /// Returns the contention counters of a lock created with
+    /// [`LockWithTimeout::new_named`], `None` for unnamed locks.
+    pub fn stats(&self) -> Option<registry::LockStatsSnapshot> {
+        self.stats.as_deref().map(LockStats::snapshot)
+    }
+
     /// Clones the `LockWithTimeout<T>`.
     ///
     /// # Returns
@@ -36,6 +155,7 @@ impl<T> LockWithTimeout<T> {
     pub fn clone(&self) -> Self {
         Self {
             state: Arc::clone(&self.state),
+            stats: self.stats.clone(),
         }
     }
 
@@ -53,11 +173,18 @@ impl<T> LockWithTimeout<T> {
         timeout_time: Option<Duration>,
     ) -> Result<RwLockWriteGuard<'a, T>, ErrorArrayItem> {
         let timeout_duration: Duration = timeout_time.unwrap_or(Duration::from_secs(1));
+        let stats: Option<&LockStats> = self.stats.as_deref();
+        let _pending = PendingWriter::new(stats);
 
         match timeout(timeout_duration, async {
             loop {
                 match self.state.try_write() {
-                    Ok(guard) => return Ok(guard),
+                    Ok(guard) => {
+                        if let Some(stats) = stats {
+                            stats.record_write();
+                        }
+                        return Ok(guard);
+                    }
                     Err(_) => {
                         time::sleep(Duration::from_millis(10)).await;
                     }
@@ -67,10 +194,15 @@ impl<T> LockWithTimeout<T> {
         .await
         {
             Ok(result) => result,
-            Err(_) => Err(ErrorArrayItem::new(
-                Errors::GeneralError,
-                String::from("Timeout while trying to acquire write lock"),
-            )),
+            Err(_) => {
+                if let Some(stats) = stats {
+                    stats.record_timeout();
+                }
+                Err(ErrorArrayItem::new(
+                    Errors::GeneralError,
+                    String::from("Timeout while trying to acquire write lock"),
+                ))
+            }
         }
     }
 
@@ -88,11 +220,17 @@ impl<T> LockWithTimeout<T> {
         timeout_time: Option<Duration>,
     ) -> Result<RwLockReadGuard<'a, T>, ErrorArrayItem> {
         let timeout_duration: Duration = timeout_time.unwrap_or(Duration::from_secs(1));
+        let stats: Option<&LockStats> = self.stats.as_deref();
 
         match timeout(timeout_duration, async {
             loop {
                 match self.state.try_read() {
-                    Ok(guard) => return Ok(guard),
+                    Ok(guard) => {
+                        if let Some(stats) = stats {
+                            stats.record_read();
+                        }
+                        return Ok(guard);
+                    }
                     Err(_) => {
                         time::sleep(Duration::from_millis(10)).await;
                     }
@@ -102,10 +240,15 @@ impl<T> LockWithTimeout<T> {
         .await
         {
             Ok(result) => result,
-            Err(_) => Err(ErrorArrayItem::new(
-                Errors::GeneralError,
-                String::from("Timeout while trying to acquire read lock"),
-            )),
+            Err(_) => {
+                if let Some(stats) = stats {
+                    stats.record_timeout();
+                }
+                Err(ErrorArrayItem::new(
+                    Errors::GeneralError,
+                    String::from("Timeout while trying to acquire read lock"),
+                ))
+            }
         }
     }
 
@@ -132,4 +275,89 @@ impl<T> LockWithTimeout<T> {
             Err(e) => Err(ErrorArrayItem::from(e)),
         }
     }
+
+    /// Like [`LockWithTimeout::try_read_with_timeout`], but for a named lock the
+    /// [`registry`] reports the lock as read locked until the guard is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_time` - An optional `Duration` specifying the timeout duration.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a tracked read lock guard on success, or an error on timeout.
+    pub async fn try_read_tracked<'a>(
+        &'a self,
+        timeout_time: Option<Duration>,
+    ) -> Result<LockReadGuard<'a, T>, ErrorArrayItem> {
+        let guard = self.try_read_with_timeout(timeout_time).await?;
+        let stats: Option<&LockStats> = self.stats.as_deref();
+        if let Some(stats) = stats {
+            stats.hold_read();
+        }
+        Ok(LockReadGuard { guard, stats })
+    }
+
+    /// Like [`LockWithTimeout::try_write_with_timeout`], but for a named lock the
+    /// [`registry`] reports the lock as write locked until the guard is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_time` - An optional `Duration` specifying the timeout duration.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a tracked write lock guard on success, or an error on timeout.
+    pub async fn try_write_tracked<'a>(
+        &'a self,
+        timeout_time: Option<Duration>,
+    ) -> Result<LockWriteGuard<'a, T>, ErrorArrayItem> {
+        let guard = self.try_write_with_timeout(timeout_time).await?;
+        let stats: Option<&LockStats> = self.stats.as_deref();
+        if let Some(stats) = stats {
+            stats.hold_write();
+        }
+        Ok(LockWriteGuard { guard, stats })
+    }
+
+    /// Runs `f` against the shared state under a read lock.
+    ///
+    /// The lock is acquired with the default timeout and released before this returns,
+    /// so the guard can not be held by accident.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure receiving a reference to the shared state.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the value returned by `f`, or an error on timeout.
+    pub async fn with_read<F, R>(&self, f: F) -> Result<R, ErrorArrayItem>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let guard = self.try_read_tracked(None).await?;
+        Ok(f(&guard))
+    }
+
+    /// Runs `f` against the shared state under a read lock and awaits the future it returns.
+    ///
+    /// The lock is held until that future completes. The future can not borrow the
+    /// state, so `f` copies out what it needs before returning it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure receiving a reference to the shared state and returning a future.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the output of the future, or an error on timeout.
+    pub async fn with_read_async<F, Fut, R>(&self, f: F) -> Result<R, ErrorArrayItem>
+    where
+        F: FnOnce(&T) -> Fut,
+        Fut: Future<Output = R>,
+    {
+        let guard = self.try_read_tracked(None).await?;
+        Ok(f(&guard).await)
+    }
 }
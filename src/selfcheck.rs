@@ -0,0 +1,299 @@
+//! Startup checks for the environment a service expects.
+//!
+//! Requirements are declared up front on a [`SelfCheck`] and all of them are evaluated by
+//! [`SelfCheck::run`], so a deployment reports every missing directory or unwritable path at
+//! once instead of failing on the first one it trips over.
+
+use std::{fmt, process::ExitCode};
+
+use nix::{
+    sys::statvfs::statvfs,
+    unistd::{access, AccessFlags, User},
+};
+
+use crate::{
+    errors::{
+        ErrorArray, ErrorArrayItem, Errors, OkWarning, ResultExt, UnifiedResult as uf,
+        WarningArray, WarningArrayItem, Warnings,
+    },
+    functions::group_digits,
+    stringy::Stringy,
+    types::PathType,
+};
+
+#[derive(Debug, Clone)]
+enum Requirement {
+    DirWritable(PathType),
+    FileReadable(PathType),
+    MinDiskFree(PathType, u64),
+    User(Stringy),
+    Command(PathType),
+}
+
+/// The result of evaluating a single requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    /// Short name of the check, for example `dir writable`.
+    pub check: Stringy,
+    /// The path or user the check was run against.
+    pub target: Stringy,
+    /// Whether the requirement was met.
+    pub passed: bool,
+    /// Why the check failed, or an advisory about a check that passed.
+    pub detail: Option<Stringy>,
+}
+
+/// Every outcome of a [`SelfCheck::run`], with the failures collected as errors.
+#[derive(Debug, Clone)]
+pub struct SelfCheckReport {
+    /// One outcome per requirement, in declaration order.
+    pub outcomes: Vec<CheckOutcome>,
+    /// An error for each failed requirement.
+    pub failures: ErrorArray,
+}
+
+impl SelfCheckReport {
+    /// Returns `true` if every requirement was met.
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.passed)
+    }
+
+    /// Logs the failures and returns the exit code a service should stop with, see
+    /// [`ErrorArray::into_exit_code`].
+    pub fn into_exit_code(self) -> ExitCode {
+        self.failures.into_exit_code()
+    }
+}
+
+// Renders the report as an aligned table, one requirement per row
+impl fmt::Display for SelfCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let check_width: usize = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.check.len())
+            .max()
+            .unwrap_or(0);
+        let target_width: usize = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.target.len())
+            .max()
+            .unwrap_or(0);
+
+        for outcome in &self.outcomes {
+            let status: &str = match outcome.passed {
+                true => "ok",
+                false => "FAIL",
+            };
+            let line: String = format!(
+                "{:<4}  {:<check_width$}  {:<target_width$}  {}",
+                status,
+                outcome.check,
+                outcome.target,
+                outcome.detail.as_deref().unwrap_or(""),
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Declarative set of environment prerequisites.
+///
+/// ```rust
+/// use dusa_collection_utils::selfcheck::SelfCheck;
+/// use dusa_collection_utils::types::PathType;
+///
+/// let report = SelfCheck::new()
+///     .require_dir_writable(PathType::Str("/tmp".into()))
+///     .require_file_readable(PathType::Str("/definitely/missing.conf".into()))
+///     .run()
+///     .unwrap();
+///
+/// assert!(!report.passed());
+/// assert_eq!(report.failures.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SelfCheck {
+    requirements: Vec<Requirement>,
+}
+
+impl SelfCheck {
+    /// Creates an empty set of requirements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires `path` to be a directory the current user can create files in.
+    pub fn require_dir_writable(mut self, path: PathType) -> Self {
+        self.requirements.push(Requirement::DirWritable(path));
+        self
+    }
+
+    /// Requires `path` to be a file the current user can read.
+    pub fn require_file_readable(mut self, path: PathType) -> Self {
+        self.requirements.push(Requirement::FileReadable(path));
+        self
+    }
+
+    /// Requires the filesystem holding `path` to have at least `bytes` available.
+    /// Less than twice that amount passes with an advisory warning.
+    pub fn require_min_disk_free(mut self, path: PathType, bytes: u64) -> Self {
+        self.requirements
+            .push(Requirement::MinDiskFree(path, bytes));
+        self
+    }
+
+    /// Requires a local user named `name` to exist.
+    pub fn require_user<S: Into<Stringy>>(mut self, name: S) -> Self {
+        self.requirements.push(Requirement::User(name.into()));
+        self
+    }
+
+    /// Requires `path` to be a file the current user can execute.
+    pub fn require_command(mut self, path: PathType) -> Self {
+        self.requirements.push(Requirement::Command(path));
+        self
+    }
+
+    /// Evaluates every requirement.
+    ///
+    /// The report is always returned, failed requirements are recorded in
+    /// [`SelfCheckReport::failures`] and advisories are returned as warnings.
+    pub fn run(&self) -> uf<SelfCheckReport> {
+        let mut failures: ErrorArray = ErrorArray::new_container();
+        let mut warnings: WarningArray = WarningArray::new_container();
+        let mut outcomes: Vec<CheckOutcome> = Vec::with_capacity(self.requirements.len());
+
+        for requirement in &self.requirements {
+            let (check, target, result) = evaluate(requirement);
+            let outcome: CheckOutcome = match result {
+                Ok(None) => CheckOutcome {
+                    check: check.into(),
+                    target,
+                    passed: true,
+                    detail: None,
+                },
+                Ok(Some(advisory)) => {
+                    let detail: Stringy = advisory
+                        .warn_mesg
+                        .clone()
+                        .unwrap_or_else(|| Stringy::from(""));
+                    warnings.push(advisory);
+                    CheckOutcome {
+                        check: check.into(),
+                        target,
+                        passed: true,
+                        detail: Some(detail),
+                    }
+                }
+                Err(err) => {
+                    let detail: Stringy = err.err_mesg.clone();
+                    failures.push(err);
+                    CheckOutcome {
+                        check: check.into(),
+                        target,
+                        passed: false,
+                        detail: Some(detail),
+                    }
+                }
+            };
+            outcomes.push(outcome);
+        }
+
+        uf::new_warn(Ok(OkWarning {
+            data: SelfCheckReport { outcomes, failures },
+            warning: warnings,
+        }))
+    }
+}
+
+type CheckResult = Result<Option<WarningArrayItem>, ErrorArrayItem>;
+
+fn evaluate(requirement: &Requirement) -> (&'static str, Stringy, CheckResult) {
+    match requirement {
+        Requirement::DirWritable(path) => (
+            "dir writable",
+            path.to_stringy_lossy(),
+            check_access(path, true, AccessFlags::W_OK | AccessFlags::X_OK),
+        ),
+        Requirement::FileReadable(path) => (
+            "file readable",
+            path.to_stringy_lossy(),
+            check_access(path, false, AccessFlags::R_OK),
+        ),
+        Requirement::MinDiskFree(path, bytes) => (
+            "disk free",
+            path.to_stringy_lossy(),
+            check_disk_free(path, *bytes),
+        ),
+        Requirement::User(name) => ("user exists", name.clone(), check_user(name)),
+        Requirement::Command(path) => (
+            "command",
+            path.to_stringy_lossy(),
+            check_access(path, false, AccessFlags::X_OK),
+        ),
+    }
+}
+
+fn check_access(path: &PathType, dir: bool, mode: AccessFlags) -> CheckResult {
+    let kind_ok: bool = match dir {
+        true => path.is_dir(),
+        false => path.is_file(),
+    };
+    if !kind_ok {
+        let expected: &str = if dir { "directory" } else { "file" };
+        return Err(ErrorArrayItem::new(
+            Errors::NotFound,
+            format!("{} is not an existing {}", path, expected),
+        ));
+    }
+
+    access(path.as_os_str(), mode).err_context(
+        Errors::PermissionDenied,
+        &format!("{} is not accessible", path),
+    )?;
+    Ok(None)
+}
+
+fn check_disk_free(path: &PathType, bytes: u64) -> CheckResult {
+    let stats = statvfs(path.as_os_str()).map_err(ErrorArrayItem::from)?;
+    let available: u64 = (stats.blocks_available() as u64) * (stats.fragment_size() as u64);
+
+    if available < bytes {
+        return Err(ErrorArrayItem::new(
+            Errors::GeneralError,
+            format!(
+                "{} bytes free on {}, {} required",
+                group_digits(available),
+                path,
+                group_digits(bytes)
+            ),
+        ));
+    }
+
+    if available / 2 < bytes {
+        return Ok(Some(WarningArrayItem::new_details(
+            Warnings::ResourceExhaustion,
+            format!(
+                "only {} bytes free on {}, less than twice the {} required",
+                group_digits(available),
+                path,
+                group_digits(bytes)
+            ),
+        )));
+    }
+
+    Ok(None)
+}
+
+fn check_user(name: &Stringy) -> CheckResult {
+    match User::from_name(name).map_err(ErrorArrayItem::from)? {
+        Some(_) => Ok(None),
+        None => Err(ErrorArrayItem::new(
+            Errors::IdentityInvalid,
+            format!("user {} does not exist", name),
+        )),
+    }
+}
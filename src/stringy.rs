@@ -1,7 +1,16 @@
-use std::{ffi::OsStr, fmt, ops::Deref, sync::Arc};
+use std::{
+    ffi::OsStr,
+    fmt,
+    ops::{Add, AddAssign, Deref},
+    str::FromStr,
+    string::FromUtf8Error,
+    sync::Arc,
+};
 
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{ErrorArrayItem, Errors};
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Stringy {
     Immutable(Arc<str>),
@@ -30,6 +39,40 @@ impl Stringy {
         Self::Immutable(data.into())
     }
 
+    /// Creates an empty `Mutable` Stringy that can hold `n` bytes without reallocating.
+    pub fn with_capacity(n: usize) -> Self {
+        Self::Mutable(String::with_capacity(n))
+    }
+
+    /// Returns the allocated capacity in bytes. An `Immutable` Stringy can not grow, so
+    /// this is its length.
+    pub fn capacity(&self) -> usize {
+        match self {
+            Stringy::Immutable(arc_str) => arc_str.len(),
+            Stringy::Mutable(s) => s.capacity(),
+        }
+    }
+
+    /// Shrinks the capacity of a `Mutable` Stringy to its length, does nothing for
+    /// `Immutable`.
+    pub fn shrink_to_fit(&mut self) {
+        if let Stringy::Mutable(s) = self {
+            s.shrink_to_fit();
+        }
+    }
+
+    /// Creates a new Stringy from UTF-8 bytes, failing on invalid sequences like
+    /// `String::from_utf8`. The error hands the bytes back.
+    pub fn from_utf8(bytes: Vec<u8>) -> Result<Self, FromUtf8Error> {
+        String::from_utf8(bytes).map(|s| Self::Immutable(s.into()))
+    }
+
+    /// Creates a new Stringy from bytes, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER` like `String::from_utf8_lossy`.
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        Self::Immutable(Arc::from(String::from_utf8_lossy(bytes)))
+    }
+
     /// Convert the Stringy to an Arc<str>
     pub fn as_arc_str(&self) -> Arc<str> {
         match self {
@@ -72,6 +115,222 @@ impl Stringy {
             Stringy::Mutable(data) => data.as_str(),
         }
     }
+
+    /// Returns the length of the string in bytes.
+    pub fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    /// Returns `true` if the string has a length of zero bytes.
+    pub fn is_empty(&self) -> bool {
+        self.as_str().is_empty()
+    }
+
+    /// Returns the uppercase equivalent as a new immutable Stringy, following
+    /// Unicode case mapping like `str::to_uppercase`.
+    pub fn to_uppercase(&self) -> Stringy {
+        Stringy::Immutable(Arc::from(self.as_str().to_uppercase()))
+    }
+
+    /// Returns the lowercase equivalent as a new immutable Stringy, following
+    /// Unicode case mapping like `str::to_lowercase`.
+    pub fn to_lowercase(&self) -> Stringy {
+        Stringy::Immutable(Arc::from(self.as_str().to_lowercase()))
+    }
+
+    /// Returns the string with leading and trailing whitespace removed.
+    ///
+    /// `Arc<str>` can not be sub-sliced, so a trimmed result is always a new allocation.
+    /// Only when there is nothing to trim from an `Immutable` Stringy is the existing
+    /// `Arc` shared instead.
+    pub fn trim(&self) -> Stringy {
+        self.trimmed(self.as_str().trim())
+    }
+
+    /// Returns the string with leading whitespace removed, see [`Stringy::trim`].
+    pub fn trim_start(&self) -> Stringy {
+        self.trimmed(self.as_str().trim_start())
+    }
+
+    /// Returns the string with trailing whitespace removed, see [`Stringy::trim`].
+    pub fn trim_end(&self) -> Stringy {
+        self.trimmed(self.as_str().trim_end())
+    }
+
+    /// Parses the string into `T`, the same as `str::parse`.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::stringy::Stringy;
+    ///
+    /// let port = Stringy::from("8080");
+    /// assert_eq!(port.parse::<u16>(), Ok(8080));
+    /// ```
+    pub fn parse<T: FromStr>(&self) -> Result<T, T::Err> {
+        self.as_str().parse()
+    }
+
+    /// Parses the string into `T`, returning `default` if it does not parse.
+    pub fn parse_or<T: FromStr>(&self, default: T) -> T {
+        self.parse().unwrap_or(default)
+    }
+
+    /// Parses the string into `T`, reporting a failure as an error of `kind` that names
+    /// the rejected value and the parser's reason.
+    pub fn parse_into_error<T>(&self, kind: Errors) -> Result<T, ErrorArrayItem>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse().map_err(|err: T::Err| {
+            ErrorArrayItem::new(
+                kind,
+                format!(
+                    "Failed to parse '{}' as {}: {}",
+                    self,
+                    std::any::type_name::<T>(),
+                    err
+                ),
+            )
+        })
+    }
+
+    /// Iterates over the lines of the string, each as a new `Immutable` Stringy.
+    /// Line endings and the final line are handled exactly like `str::lines`.
+    pub fn lines(&self) -> impl Iterator<Item = Stringy> + '_ {
+        self.as_str()
+            .lines()
+            .map(|line| Stringy::Immutable(Arc::from(line)))
+    }
+
+    /// Iterates over the words of the string split on ASCII whitespace, each as a new
+    /// `Immutable` Stringy. Runs of whitespace never produce empty words.
+    pub fn words(&self) -> impl Iterator<Item = Stringy> + '_ {
+        self.as_str()
+            .split_ascii_whitespace()
+            .map(|word| Stringy::Immutable(Arc::from(word)))
+    }
+
+    /// Replaces every match of `from` with `to`.
+    ///
+    /// If `from` does not occur the result is a clone of `self`, so an `Immutable`
+    /// Stringy keeps sharing its `Arc` instead of allocating.
+    pub fn replace(&self, from: &str, to: &str) -> Stringy {
+        match self.as_str().contains(from) {
+            true => Stringy::Immutable(Arc::from(self.as_str().replace(from, to))),
+            false => self.clone(),
+        }
+    }
+
+    /// Replaces the first `n` matches of `from` with `to`, see [`Stringy::replace`].
+    pub fn replacen(&self, from: &str, to: &str, n: usize) -> Stringy {
+        match n > 0 && self.as_str().contains(from) {
+            true => Stringy::Immutable(Arc::from(self.as_str().replacen(from, to, n))),
+            false => self.clone(),
+        }
+    }
+
+    fn trimmed(&self, trimmed: &str) -> Stringy {
+        match self {
+            Stringy::Immutable(arc_str) if trimmed.len() == arc_str.len() => {
+                Stringy::Immutable(Arc::clone(arc_str))
+            }
+            _ => Stringy::Immutable(Arc::from(trimmed)),
+        }
+    }
+
+    /// Returns `true` if the string starts with the given pattern.
+    pub fn starts_with<P: StringyPattern>(&self, pat: P) -> bool {
+        pat.prefix_of(self.as_str())
+    }
+
+    /// Returns `true` if the string ends with the given pattern.
+    pub fn ends_with<P: StringyPattern>(&self, pat: P) -> bool {
+        pat.suffix_of(self.as_str())
+    }
+
+    /// Returns `true` if the given pattern matches anywhere in the string.
+    pub fn contains<P: StringyPattern>(&self, pat: P) -> bool {
+        pat.contained_in(self.as_str())
+    }
+}
+
+/// Patterns accepted by [`Stringy::starts_with`], [`Stringy::ends_with`] and
+/// [`Stringy::contains`].
+///
+/// This mirrors the std `Pattern` trait, which can not be named on stable, for the
+/// pattern types in common use: `char`, string slices, slices or arrays of `char` and
+/// `FnMut(char) -> bool` closures or functions such as `char::is_numeric`.
+pub trait StringyPattern {
+    /// Returns `true` if the pattern matches anywhere in `haystack`.
+    fn contained_in(self, haystack: &str) -> bool;
+    /// Returns `true` if the pattern matches at the start of `haystack`.
+    fn prefix_of(self, haystack: &str) -> bool;
+    /// Returns `true` if the pattern matches at the end of `haystack`.
+    fn suffix_of(self, haystack: &str) -> bool;
+}
+
+macro_rules! impl_stringy_pattern {
+    ($($ty:ty),*) => {
+        $(
+            impl StringyPattern for $ty {
+                fn contained_in(self, haystack: &str) -> bool {
+                    haystack.contains(self)
+                }
+
+                fn prefix_of(self, haystack: &str) -> bool {
+                    haystack.starts_with(self)
+                }
+
+                fn suffix_of(self, haystack: &str) -> bool {
+                    haystack.ends_with(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_stringy_pattern!(char, &str, &&str, &String, &[char]);
+
+impl<const N: usize> StringyPattern for [char; N] {
+    fn contained_in(self, haystack: &str) -> bool {
+        haystack.contains(self)
+    }
+
+    fn prefix_of(self, haystack: &str) -> bool {
+        haystack.starts_with(self)
+    }
+
+    fn suffix_of(self, haystack: &str) -> bool {
+        haystack.ends_with(self)
+    }
+}
+
+impl<F: FnMut(char) -> bool> StringyPattern for F {
+    fn contained_in(self, haystack: &str) -> bool {
+        haystack.contains(self)
+    }
+
+    fn prefix_of(self, haystack: &str) -> bool {
+        haystack.starts_with(self)
+    }
+
+    fn suffix_of(self, haystack: &str) -> bool {
+        haystack.ends_with(self)
+    }
+}
+
+impl StringyPattern for &Stringy {
+    fn contained_in(self, haystack: &str) -> bool {
+        haystack.contains(self.as_str())
+    }
+
+    fn prefix_of(self, haystack: &str) -> bool {
+        haystack.starts_with(self.as_str())
+    }
+
+    fn suffix_of(self, haystack: &str) -> bool {
+        haystack.ends_with(self.as_str())
+    }
 }
 
 impl Deref for Stringy {
@@ -134,6 +393,64 @@ impl fmt::Display for Stringy {
     }
 }
 
+// Compares the text only, so `assert_eq!(stringy, "value")` works in either order
+macro_rules! impl_stringy_eq {
+    ($($ty:ty),*) => {
+        $(
+            impl PartialEq<$ty> for Stringy {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.as_str() == AsRef::<str>::as_ref(other)
+                }
+            }
+
+            impl PartialEq<Stringy> for $ty {
+                fn eq(&self, other: &Stringy) -> bool {
+                    AsRef::<str>::as_ref(self) == other.as_str()
+                }
+            }
+        )*
+    };
+}
+
+impl_stringy_eq!(str, &str, String);
+
+// Joins into a buffer sized for both sides, so it never grows while joining
+fn concat(left: &str, right: &str) -> Stringy {
+    let mut joined: String = String::with_capacity(left.len() + right.len());
+    joined.push_str(left);
+    joined.push_str(right);
+    Stringy::Immutable(Arc::from(joined))
+}
+
+impl Add<Stringy> for Stringy {
+    type Output = Stringy;
+
+    fn add(self, rhs: Stringy) -> Stringy {
+        concat(self.as_str(), rhs.as_str())
+    }
+}
+
+impl Add<&str> for Stringy {
+    type Output = Stringy;
+
+    fn add(self, rhs: &str) -> Stringy {
+        concat(self.as_str(), rhs)
+    }
+}
+
+// Extends in place through `mutate`, converting an Immutable Stringy once
+impl AddAssign<&str> for Stringy {
+    fn add_assign(&mut self, rhs: &str) {
+        self.mutate(|s| s.push_str(rhs));
+    }
+}
+
+impl AddAssign<Stringy> for Stringy {
+    fn add_assign(&mut self, rhs: Stringy) {
+        self.mutate(|s| s.push_str(rhs.as_str()));
+    }
+}
+
 impl From<String> for Stringy {
     fn from(s: String) -> Self {
         Self::Immutable(Arc::from(s.as_str()))
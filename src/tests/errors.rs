@@ -1,15 +1,29 @@
 #[cfg(test)]
 mod tests {
+    use crate::errors::collect_results;
+    use crate::errors::drain_global;
+    use crate::errors::get_display_style;
+    use crate::errors::global;
+    use crate::errors::AsyncErrorArray;
+    use crate::errors::DisplayStyle;
     use crate::errors::ErrorArray;
     use crate::errors::ErrorArrayItem;
+    use crate::errors::ErrorCategory;
     use crate::errors::Errors;
+    use crate::errors::KindKey;
     use crate::errors::OkWarning;
+    use crate::errors::OverflowPolicy;
+    use crate::errors::ResultExt;
     use crate::errors::UnifiedResult;
     use crate::errors::WarningArray;
     use crate::errors::WarningArrayItem;
+    use crate::errors::WarningSeverity;
     use crate::errors::Warnings;
+    use crate::functions::current_timestamp;
+    use crate::stringy::Stringy;
 
     // use super::*;
+    use colored::Colorize;
     use std::io;
     use std::net;
     use std::net::AddrParseError;
@@ -21,7 +35,7 @@ mod tests {
         let error_item =
             ErrorArrayItem::new(Errors::OpeningFile, String::from("Failed to open file"));
         assert_eq!(error_item.err_type, Errors::OpeningFile);
-        assert_eq!(error_item.err_mesg, "Failed to open file".into());
+        assert_eq!(error_item.err_mesg, "Failed to open file");
     }
 
     #[test]
@@ -55,7 +69,7 @@ mod tests {
         assert_eq!(error_array.len(), 2);
 
         // Displaying and clearing the array
-        error_array.clone().display(false);
+        error_array.display();
         assert_eq!(error_array.len(), 0);
     }
 
@@ -84,13 +98,13 @@ mod tests {
         let io_error = io::Error::new(io::ErrorKind::Other, "I/O error");
         let error_item: ErrorArrayItem = io_error.into();
         assert_eq!(error_item.err_type, Errors::InputOutput);
-        assert_eq!(error_item.err_mesg, "I/O error".into());
+        assert_eq!(error_item.err_mesg, "I/O error");
 
         // Converting net::AddrParseError
         let addr_error: AddrParseError = "invalid address".parse::<net::IpAddr>().unwrap_err();
         let error_item: ErrorArrayItem = addr_error.into();
         assert_eq!(error_item.err_type, Errors::InputOutput);
-        assert_eq!(error_item.err_mesg, "invalid IP address syntax".into());
+        assert_eq!(error_item.err_mesg, "invalid IP address syntax");
 
         // Converting mpsc::SendError
         let (sender, receiver) = mpsc::channel::<i32>();
@@ -98,7 +112,7 @@ mod tests {
         let send_error: mpsc::SendError<i32> = sender.send(1).unwrap_err();
         let error_item: ErrorArrayItem = send_error.into();
         assert_eq!(error_item.err_type, Errors::InputOutput);
-        assert_eq!(error_item.err_mesg, "sending on a closed channel".into());
+        assert_eq!(error_item.err_mesg, "sending on a closed channel");
 
         // // Converting SystemTimeError
         // let system_time_error: SystemTime = SystemTime::now() - SystemTime::UNIX_EPOCH;
@@ -168,7 +182,7 @@ mod tests {
         assert_eq!(error_array.len(), 1);
         let errors = error_array.0.read().unwrap();
         assert_eq!(errors[0].err_type, Errors::OpeningFile);
-        assert_eq!(errors[0].err_mesg, "Failed to open file".into());
+        assert_eq!(errors[0].err_mesg, "Failed to open file");
     }
 
     #[test]
@@ -197,7 +211,27 @@ mod tests {
         let io_error = io::Error::new(io::ErrorKind::Other, "io error");
         let error_item: ErrorArrayItem = io_error.into();
         assert_eq!(error_item.err_type, Errors::InputOutput);
-        assert_eq!(error_item.err_mesg, "io error".into());
+        assert_eq!(error_item.err_mesg, "io error");
+    }
+
+    #[test]
+    fn test_error_array_item_from_io_error_kind() {
+        let cases = [
+            (io::ErrorKind::NotFound, Errors::NotFound),
+            (io::ErrorKind::PermissionDenied, Errors::PermissionDenied),
+            (io::ErrorKind::TimedOut, Errors::Timeout),
+            (io::ErrorKind::AlreadyExists, Errors::AlreadyExists),
+            (io::ErrorKind::UnexpectedEof, Errors::InputOutput),
+            (io::ErrorKind::Other, Errors::InputOutput),
+        ];
+        for (kind, expected) in cases {
+            let item = ErrorArrayItem::from(io::Error::new(kind, "io error"));
+            assert_eq!(item.err_type, expected, "{:?}", kind);
+            assert_eq!(item.err_mesg, "io error");
+        }
+
+        let missing = std::fs::File::open("/definitely/missing/file").unwrap_err();
+        assert_eq!(ErrorArrayItem::from(missing).err_type, Errors::NotFound);
     }
 
     #[test]
@@ -206,7 +240,7 @@ mod tests {
             "invalid address".parse::<net::IpAddr>().unwrap_err();
         let error_item: ErrorArrayItem = addr_parse_error.into();
         assert_eq!(error_item.err_type, Errors::InputOutput);
-        assert_eq!(error_item.err_mesg, "invalid IP address syntax".into());
+        assert_eq!(error_item.err_mesg, "invalid IP address syntax");
     }
 
     #[test]
@@ -215,7 +249,7 @@ mod tests {
         let result: ErrorArrayItem = errors.pop();
 
         assert_eq!(result.err_type, Errors::GeneralError);
-        assert_eq!(result.err_mesg, "No previous error".into());
+        assert_eq!(result.err_mesg, "No previous error");
     }
 
     #[test]
@@ -236,7 +270,7 @@ mod tests {
         // Ensure the array is empty after popping
         let empty_result: ErrorArrayItem = errors.pop();
         assert_eq!(empty_result.err_type, Errors::GeneralError);
-        assert_eq!(empty_result.err_mesg, "No previous error".into());
+        assert_eq!(empty_result.err_mesg, "No previous error");
     }
 
     #[test]
@@ -259,7 +293,7 @@ mod tests {
         // Ensure the array is empty after popping all errors
         let empty_result: ErrorArrayItem = errors.pop();
         assert_eq!(empty_result.err_type, Errors::GeneralError);
-        assert_eq!(empty_result.err_mesg, "No previous error".into());
+        assert_eq!(empty_result.err_mesg, "No previous error");
     }
 
     #[test]
@@ -274,4 +308,2420 @@ mod tests {
 
         assert_eq!(okwarning.strip(), String::new())
     }
+
+    #[test]
+    fn test_warning_weights() {
+        let all = [
+            Warnings::Warning,
+            Warnings::OutdatedVersion,
+            Warnings::MisAlignedChunk,
+            Warnings::FileNotDeleted,
+            Warnings::ConnectionLost,
+            Warnings::ResourceExhaustion,
+            Warnings::UnexpectedBehavior,
+            Warnings::UnexpectedConfiguration,
+        ];
+
+        for warning in all {
+            // Exhaustive so a new variant has to be given a weight here as well
+            let expected: u8 = match warning {
+                Warnings::ResourceExhaustion => 7,
+                Warnings::ConnectionLost => 6,
+                Warnings::UnexpectedBehavior => 5,
+                Warnings::FileNotDeleted => 4,
+                Warnings::MisAlignedChunk => 3,
+                Warnings::UnexpectedConfiguration => 2,
+                Warnings::OutdatedVersion => 1,
+                Warnings::Warning => 0,
+            };
+            assert_eq!(warning.weight(), expected);
+        }
+    }
+
+    #[test]
+    fn test_warning_sort_by_severity() {
+        let mut warnings = WarningArray::new(vec![
+            WarningArrayItem::new(Warnings::OutdatedVersion),
+            WarningArrayItem::new_details(Warnings::ConnectionLost, String::from("first")),
+            WarningArrayItem::new(Warnings::ResourceExhaustion),
+            WarningArrayItem::new_details(Warnings::ConnectionLost, String::from("second")),
+            WarningArrayItem::new(Warnings::Warning),
+        ]);
+
+        warnings.sort_by_severity();
+
+        let sorted = warnings.0.read().unwrap();
+        let order: Vec<Warnings> = sorted.iter().map(|item| item.warn_type).collect();
+        assert_eq!(
+            order,
+            vec![
+                Warnings::ResourceExhaustion,
+                Warnings::ConnectionLost,
+                Warnings::ConnectionLost,
+                Warnings::OutdatedVersion,
+                Warnings::Warning,
+            ]
+        );
+        // Equal weights keep their insertion order
+        assert_eq!(sorted[1].warn_mesg.as_deref(), Some("first"));
+        assert_eq!(sorted[2].warn_mesg.as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn test_warning_display_sorted_clears() {
+        let warnings = WarningArray::new(vec![
+            WarningArrayItem::new(Warnings::Warning),
+            WarningArrayItem::new(Warnings::ResourceExhaustion),
+            WarningArrayItem::new(Warnings::FileNotDeleted),
+        ]);
+
+        let output = warnings.clone().display_sorted_to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Warning: resource exhaustion",
+                "Warning: file not deleted",
+                "Warning: generic warning",
+            ]
+        );
+        assert_eq!(warnings.len(), 0);
+    }
+
+    #[test]
+    fn test_warning_most_severe() {
+        let empty = WarningArray::new_container();
+        assert!(empty.most_severe().is_none());
+
+        let warnings = WarningArray::new(vec![
+            WarningArrayItem::new(Warnings::FileNotDeleted),
+            WarningArrayItem::new_details(Warnings::ConnectionLost, String::from("first")),
+            WarningArrayItem::new_details(Warnings::ConnectionLost, String::from("second")),
+        ]);
+
+        let most_severe = warnings.most_severe().unwrap();
+        assert_eq!(most_severe.warn_type, Warnings::ConnectionLost);
+        assert_eq!(most_severe.warn_mesg.as_deref(), Some("first"));
+        assert_eq!(warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_error_context_builder() {
+        let err = ErrorArrayItem::new(Errors::PermissionDenied, "Access denied")
+            .context("path", "/etc/shadow")
+            .context("uid", 1000);
+
+        assert_eq!(err.context.len(), 2);
+        assert_eq!(err.get_context("path").unwrap().as_str(), "/etc/shadow");
+        assert_eq!(err.get_context("uid").unwrap().as_str(), "1000");
+        assert!(err.get_context("gid").is_none());
+    }
+
+    #[test]
+    fn test_error_context_display() {
+        let plain = ErrorArrayItem::new(Errors::NotFound, "Missing");
+        assert_eq!(plain.format_with(DisplayStyle::Plain), "NotFound: Missing");
+
+        let with_context = plain.context("path", "/tmp/x").context("op", "read");
+        assert_eq!(
+            with_context.format_with(DisplayStyle::Plain),
+            "NotFound: Missing [path=/tmp/x, op=read]"
+        );
+    }
+
+    #[test]
+    fn test_error_context_serde_round_trip() {
+        let err = ErrorArrayItem::new(Errors::ReadingFile, "Failed to read")
+            .context("path", "/var/log/app.log");
+
+        let json = serde_json::to_string(&err).unwrap();
+        let decoded: ErrorArrayItem = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, err);
+
+        // Items serialized before the context field existed still deserialize
+        let legacy = r#"{"err_type":"ReadingFile","err_mesg":"Failed to read"}"#;
+        let decoded: ErrorArrayItem = serde_json::from_str(legacy).unwrap();
+        assert!(decoded.context.is_empty());
+    }
+
+    #[test]
+    fn test_error_array_contains_type() {
+        let empty = ErrorArray::new_container();
+        assert!(!empty.contains_type(Errors::NotFound));
+
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::NotFound, "Missing"),
+            ErrorArrayItem::new(Errors::Timeout, "Too slow"),
+        ]);
+        assert!(errors.contains_type(Errors::Timeout));
+        assert!(!errors.contains_type(Errors::PermissionDenied));
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_error_array_find_first() {
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "first"),
+            ErrorArrayItem::new(Errors::NotFound, "Missing"),
+            ErrorArrayItem::new(Errors::Timeout, "second"),
+        ]);
+
+        let found = errors.find_first(Errors::Timeout).unwrap();
+        assert_eq!(found.err_mesg, "first");
+        assert!(errors.find_first(Errors::Network).is_none());
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_warning_array_contains_type() {
+        let warnings = WarningArray::new(vec![WarningArrayItem::new(Warnings::ConnectionLost)]);
+        assert!(warnings.contains_type(Warnings::ConnectionLost));
+        assert!(!warnings.contains_type(Warnings::OutdatedVersion));
+    }
+
+    #[test]
+    fn test_error_timestamp() {
+        let before = current_timestamp();
+        let err = ErrorArrayItem::new(Errors::Timeout, "Too slow");
+        let after = current_timestamp();
+
+        assert!(err.timestamp() >= before && err.timestamp() <= after);
+        assert_eq!(
+            err.display_with_time(),
+            format!("[{}] {}", err.timestamp(), err)
+        );
+    }
+
+    #[test]
+    fn test_error_timestamp_serde_default() {
+        let legacy = r#"{"err_type":"Timeout","err_mesg":"Too slow"}"#;
+        let decoded: ErrorArrayItem = serde_json::from_str(legacy).unwrap();
+        assert_eq!(decoded.timestamp(), 0);
+
+        let renamed = r#"{"err_type":"Timeout","err_mesg":"Too slow","timestamp":42}"#;
+        let decoded: ErrorArrayItem = serde_json::from_str(renamed).unwrap();
+        assert_eq!(decoded.created_at, 42);
+
+        let err = ErrorArrayItem::new(Errors::Timeout, "Too slow");
+        let json = serde_json::to_string(&err).unwrap();
+        let decoded: ErrorArrayItem = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.timestamp(), err.timestamp());
+    }
+
+    #[test]
+    fn test_error_array_display_by_time() {
+        let mut newer = ErrorArrayItem::new(Errors::Timeout, "newer");
+        newer.created_at = 200;
+        let mut older = ErrorArrayItem::new(Errors::NotFound, "older");
+        older.created_at = 100;
+
+        let errors = ErrorArray::new(vec![newer, older]);
+        let output = errors.display_by_time_to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("[100] ") && lines[0].ends_with("older"));
+        assert!(lines[1].starts_with("[200] ") && lines[1].ends_with("newer"));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.display_by_time_to_string(), output);
+
+        errors.display_by_time();
+        assert_eq!(errors.len(), 0);
+    }
+
+    #[test]
+    fn test_error_equality_ignores_created_at() {
+        let mut first = ErrorArrayItem::new(Errors::NotFound, "Missing");
+        first.created_at = 100;
+        let mut second = first.clone();
+        second.created_at = 200;
+
+        assert_eq!(first, second);
+        assert_eq!(first.cmp(&second), std::cmp::Ordering::Equal);
+        assert_ne!(first, second.clone().context("path", "/tmp"));
+    }
+
+    #[test]
+    fn test_error_array_retain() {
+        let mut errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "first"),
+            ErrorArrayItem::new(Errors::NotFound, "Missing"),
+            ErrorArrayItem::new(Errors::Timeout, "second"),
+        ]);
+
+        errors.retain(|item| item.err_type == Errors::Timeout);
+
+        let remaining = errors.0.read().unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].err_mesg, "first");
+        assert_eq!(remaining[1].err_mesg, "second");
+    }
+
+    #[test]
+    fn test_warning_array_retain() {
+        let mut warnings = WarningArray::new(vec![
+            WarningArrayItem::new(Warnings::Warning),
+            WarningArrayItem::new(Warnings::ConnectionLost),
+        ]);
+
+        warnings.retain(|item| item.warn_type != Warnings::Warning);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings.contains_type(Warnings::ConnectionLost));
+    }
+
+    #[test]
+    fn test_error_array_retain_poisoned() {
+        let mut errors = ErrorArray::new(vec![ErrorArrayItem::new(Errors::Timeout, "kept")]);
+
+        let poisoner = errors.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.0.write().unwrap();
+            panic!("poisoning the lock");
+        })
+        .join();
+
+        errors.retain(|item| item.err_type != Errors::Timeout);
+        assert_eq!(errors.try_len(), None);
+        assert_eq!(errors.len(), 0);
+    }
+
+    #[test]
+    fn test_error_array_into_vec() {
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "first"),
+            ErrorArrayItem::new(Errors::NotFound, "second"),
+        ]);
+        let other_holder = errors.clone();
+
+        let items = errors.into_vec();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].err_mesg, "first");
+        assert_eq!(items[1].err_type, Errors::NotFound);
+
+        // Shared holders observe an empty but usable array
+        assert_eq!(other_holder.len(), 0);
+    }
+
+    #[test]
+    fn test_warning_array_into_vec() {
+        let warnings = WarningArray::new(vec![WarningArrayItem::new(Warnings::ConnectionLost)]);
+        let mut other_holder = warnings.clone();
+
+        let items = warnings.into_vec();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].warn_type, Warnings::ConnectionLost);
+
+        other_holder.push(WarningArrayItem::new(Warnings::Warning));
+        assert_eq!(other_holder.len(), 1);
+    }
+
+    #[test]
+    fn test_error_array_count_by_type() {
+        assert!(ErrorArray::new_container().count_by_type().is_empty());
+
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "first"),
+            ErrorArrayItem::new(Errors::NotFound, "Missing"),
+            ErrorArrayItem::new(Errors::Timeout, "second"),
+        ]);
+
+        let counts = errors.count_by_type();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&Errors::Timeout], 2);
+        assert_eq!(counts[&Errors::NotFound], 1);
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_warning_array_count_by_type() {
+        let warnings = WarningArray::new(vec![
+            WarningArrayItem::new(Warnings::ConnectionLost),
+            WarningArrayItem::new(Warnings::ConnectionLost),
+            WarningArrayItem::new(Warnings::Warning),
+        ]);
+
+        let counts = warnings.count_by_type();
+        assert_eq!(counts[&Warnings::ConnectionLost], 2);
+        assert_eq!(counts[&Warnings::Warning], 1);
+        assert!(!counts.contains_key(&Warnings::OutdatedVersion));
+    }
+
+    #[test]
+    fn test_error_array_query_by_kind() {
+        let mut errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::PermissionDenied, "/etc/shadow"),
+            ErrorArrayItem::new(Errors::Timeout, "socket"),
+            ErrorArrayItem::new(Errors::PermissionDenied, "/root"),
+        ]);
+
+        assert!(errors.contains_kind(Errors::PermissionDenied));
+
+        let denied = errors.filter_by_kind(Errors::PermissionDenied);
+        assert_eq!(denied.len(), 2);
+        assert_eq!(denied[0].err_mesg, "/etc/shadow");
+        assert_eq!(denied[1].err_mesg, "/root");
+        assert!(errors.filter_by_kind(Errors::NotFound).is_empty());
+
+        errors.retain_kind(Errors::Timeout);
+        assert_eq!(errors.len(), 1);
+        assert!(!errors.contains_kind(Errors::PermissionDenied));
+    }
+
+    #[test]
+    fn test_error_array_query_by_kind_empty() {
+        let mut errors = ErrorArray::new_container();
+
+        assert!(!errors.contains_kind(Errors::PermissionDenied));
+        assert!(errors.filter_by_kind(Errors::PermissionDenied).is_empty());
+
+        errors.retain_kind(Errors::Timeout);
+        assert_eq!(errors.len(), 0);
+    }
+
+    #[test]
+    fn test_error_array_display_to_string() {
+        let first = ErrorArrayItem::new(Errors::OpeningFile, "Failed to open file");
+        let second = ErrorArrayItem::new(Errors::Timeout, "socket");
+        let errors = ErrorArray::new(vec![first.clone(), second.clone()]);
+        let handle = errors.clone();
+
+        let output = errors.display_to_string();
+        assert_eq!(output, format!("{}\n{}", first, second));
+        assert_eq!(handle.len(), 0);
+
+        assert_eq!(ErrorArray::new_container().display_to_string(), "");
+    }
+
+    #[test]
+    fn test_warning_array_display_to_string() {
+        let warning = WarningArrayItem::new_details(Warnings::OutdatedVersion, "v1".to_owned());
+        let warnings = WarningArray::new(vec![warning.clone(), warning.clone()]);
+        let handle = warnings.clone();
+
+        let output = warnings.display_to_string();
+        assert_eq!(output, format!("{}\n{}", warning, warning));
+        assert_eq!(handle.len(), 0);
+    }
+
+    #[test]
+    fn test_unified_result_map_keeps_variant() {
+        let result: UnifiedResult<u32> = UnifiedResult::new(Ok(2));
+        match result.map(|n| n * 2) {
+            UnifiedResult::ResultNoWarns(Ok(n)) => assert_eq!(n, 4),
+            _ => panic!("Expected ResultNoWarns(Ok)"),
+        }
+
+        let result: UnifiedResult<u32> =
+            UnifiedResult::new_warn(Err(ErrorArrayItem::new(Errors::Timeout, "socket")));
+        let result = result.map(|n| n * 2).map_err(|e| e.context("retry", 3));
+        match result {
+            UnifiedResult::ResultWarning(Err(e)) => {
+                assert_eq!(e.err_type, Errors::Timeout);
+                assert_eq!(e.get_context("retry").unwrap().as_str(), "3");
+            }
+            _ => panic!("Expected ResultWarning(Err)"),
+        }
+    }
+
+    #[test]
+    fn test_unified_result_warnings_survive_chain() {
+        let warning = WarningArrayItem::new(Warnings::OutdatedVersion);
+        let result: UnifiedResult<u32> =
+            UnifiedResult::new_warn(Ok(OkWarning::new_from_item(1, warning)));
+
+        let chained = result.map(|n| n + 1).and_then(|n| {
+            UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+                n * 10,
+                WarningArrayItem::new(Warnings::MisAlignedChunk),
+            )))
+        });
+
+        let warnings = match &chained {
+            UnifiedResult::ResultWarning(Ok(d)) => d.warning.clone(),
+            _ => panic!("Expected ResultWarning(Ok)"),
+        };
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.contains_type(Warnings::OutdatedVersion));
+        assert!(warnings.contains_type(Warnings::MisAlignedChunk));
+
+        assert_eq!(chained.uf_unwrap().unwrap(), 20);
+        assert_eq!(warnings.len(), 0);
+    }
+
+    #[test]
+    fn test_unified_result_and_then_error() {
+        let result: UnifiedResult<u32> = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+            1,
+            WarningArrayItem::new(Warnings::Warning),
+        )));
+        let chained: UnifiedResult<u32> = result
+            .and_then(|_| UnifiedResult::new(Err(ErrorArrayItem::new(Errors::NotFound, "gone"))));
+        assert!(chained.is_err());
+        assert_eq!(chained.get_err().unwrap().err_type, Errors::NotFound);
+
+        let result: UnifiedResult<u32> = UnifiedResult::new(Ok(1));
+        match result.and_then(|n| UnifiedResult::new(Ok(n + 1))) {
+            UnifiedResult::ResultNoWarns(Ok(n)) => assert_eq!(n, 2),
+            _ => panic!("Expected ResultNoWarns(Ok)"),
+        }
+    }
+
+    #[test]
+    fn test_created_at_populated() {
+        let before = current_timestamp();
+        let err = ErrorArrayItem::from(std::io::Error::other("disk"));
+        let warning = WarningArrayItem::new_details(Warnings::Warning, "late".to_owned());
+        let after = current_timestamp();
+
+        assert!(err.created_at >= before && err.created_at <= after);
+        assert!(warning.created_at >= before && warning.created_at <= after);
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["timestamp"], err.created_at);
+        assert!(json.get("created_at").is_none());
+
+        let legacy = r#"{"err_type":"Timeout","err_mesg":"Too slow","created_at":42}"#;
+        let decoded: ErrorArrayItem = serde_json::from_str(legacy).unwrap();
+        assert_eq!(decoded.created_at, 42);
+    }
+
+    fn double_checked(input: &str) -> UnifiedResult<u32> {
+        let value: u32 = crate::ok_or_return!(input.parse::<u32>());
+        let doubled: u32 = crate::ok_or_return!(value
+            .checked_mul(2)
+            .ok_or_else(|| ErrorArrayItem::new(Errors::GeneralError, "overflow")));
+        UnifiedResult::new(Ok(doubled))
+    }
+
+    #[test]
+    fn test_ok_or_return_macro() {
+        assert_eq!(double_checked("21").unwrap(), 42);
+
+        let err = double_checked("abc").get_err().unwrap();
+        assert_eq!(err.err_type, Errors::InputOutput);
+
+        let err = double_checked(&u32::MAX.to_string()).get_err().unwrap();
+        assert_eq!(err.err_type, Errors::GeneralError);
+        assert_eq!(err.err_mesg, "overflow");
+    }
+
+    #[test]
+    fn test_unified_result_from_error_item() {
+        let result: UnifiedResult<()> = ErrorArrayItem::new(Errors::NotFound, "gone").into();
+        match result {
+            UnifiedResult::ResultNoWarns(Err(e)) => assert_eq!(e.err_type, Errors::NotFound),
+            _ => panic!("Expected ResultNoWarns(Err)"),
+        }
+    }
+
+    #[cfg(feature = "nightly-try")]
+    fn double_with_question_mark(input: &str) -> UnifiedResult<u32> {
+        let value: u32 = input.parse::<u32>()?;
+        UnifiedResult::new(Ok(value * 2))
+    }
+
+    #[cfg(feature = "nightly-try")]
+    #[test]
+    fn test_question_mark_on_result() {
+        assert_eq!(double_with_question_mark("21").unwrap(), 42);
+        assert!(double_with_question_mark("abc").is_err());
+    }
+
+    #[test]
+    fn test_with_context() {
+        let err = ErrorArrayItem::new(Errors::OpeningFile, "No such file or directory")
+            .with_context("opening config at /etc/app.conf");
+        assert_eq!(err.err_type, Errors::OpeningFile);
+        assert_eq!(
+            err.err_mesg,
+            "opening config at /etc/app.conf: No such file or directory"
+        );
+
+        let warning = WarningArrayItem::new(Warnings::FileNotDeleted).with_context("/tmp/lock");
+        assert_eq!(warning.warn_mesg.as_deref(), Some("/tmp/lock"));
+
+        let warning = WarningArrayItem::new_details(Warnings::FileNotDeleted, "busy".to_owned())
+            .with_context("/tmp/lock");
+        assert_eq!(warning.warn_mesg.as_deref(), Some("/tmp/lock: busy"));
+    }
+
+    #[test]
+    fn test_into_result_with_warnings() {
+        let result: UnifiedResult<u32> = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+            7,
+            WarningArrayItem::new(Warnings::OutdatedVersion),
+        )));
+        let (data, warnings) = result.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(data, 7);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings.contains_type(Warnings::OutdatedVersion));
+
+        let result: UnifiedResult<u32> = Ok(3).into();
+        let (data, warnings) = result.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(data, 3);
+        assert_eq!(warnings.len(), 0);
+
+        let result: UnifiedResult<u32> = Err(ErrorArrayItem::new(Errors::NotFound, "gone")).into();
+        let err = result.into_result_with_warnings().unwrap_err();
+        assert_eq!(err.err_type, Errors::NotFound);
+    }
+
+    #[test]
+    fn test_error_display_styles() {
+        let err = ErrorArrayItem::new(Errors::PermissionDenied, "/etc/shadow").context("uid", 1000);
+
+        assert_eq!(
+            err.format_with(DisplayStyle::Plain),
+            "PermissionDenied: /etc/shadow [uid=1000]"
+        );
+        assert_eq!(
+            err.format_styled(DisplayStyle::Pretty, false),
+            "PermissionDenied: /etc/shadow [uid=1000]"
+        );
+        assert_eq!(
+            err.format_with(DisplayStyle::Short),
+            "PermissionDenied: /etc/shadow"
+        );
+        assert_eq!(err.to_string(), err.format_with(get_display_style()));
+    }
+
+    // Clears the process wide color override when the test ends, even on a failed assert
+    struct ColorOverride;
+
+    impl ColorOverride {
+        fn force(enabled: bool) -> Self {
+            colored::control::set_override(enabled);
+            ColorOverride
+        }
+    }
+
+    impl Drop for ColorOverride {
+        fn drop(&mut self) {
+            colored::control::unset_override();
+        }
+    }
+
+    #[test]
+    fn test_error_display_pretty_colored() {
+        let _color = ColorOverride::force(true);
+        let err = ErrorArrayItem::new(Errors::PermissionDenied, "/etc/shadow").context("uid", 1000);
+
+        let pretty: String = err.format_styled(DisplayStyle::Pretty, true);
+        assert_eq!(
+            pretty,
+            format!(
+                "{}: /etc/shadow [uid=1000]",
+                "PermissionDenied".bold().red()
+            )
+        );
+        assert!(pretty.contains("\x1b["));
+        assert_eq!(
+            err.format_styled(DisplayStyle::Plain, true),
+            "PermissionDenied: /etc/shadow [uid=1000]"
+        );
+    }
+
+    #[test]
+    fn test_error_array_into_iter() {
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "first"),
+            ErrorArrayItem::new(Errors::NotFound, "second"),
+            ErrorArrayItem::new(Errors::Timeout, "third"),
+        ]);
+        let handle = errors.clone();
+
+        let timeouts: Vec<Stringy> = errors
+            .into_iter()
+            .filter(|item| item.err_type == Errors::Timeout)
+            .map(|item| item.err_mesg)
+            .collect();
+        assert_eq!(
+            timeouts,
+            vec![Stringy::from("first"), Stringy::from("third")]
+        );
+        assert_eq!(handle.len(), 0);
+
+        let mut seen = 0;
+        for _ in ErrorArray::new_container() {
+            seen += 1;
+        }
+        assert_eq!(seen, 0);
+    }
+
+    #[test]
+    fn test_error_array_serde_round_trip() {
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "socket"),
+            ErrorArrayItem::new(Errors::NotFound, "/tmp/x").context("op", "read"),
+        ]);
+
+        let json = serde_json::to_string(&errors).unwrap();
+        assert!(json.starts_with('['));
+
+        let decoded: ErrorArray = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.into_vec(), errors.into_vec());
+    }
+
+    #[test]
+    fn test_warning_array_serde_round_trip() {
+        let warnings = WarningArray::new(vec![
+            WarningArrayItem::new(Warnings::OutdatedVersion),
+            WarningArrayItem::new_details(Warnings::FileNotDeleted, "/tmp/lock".to_owned()),
+        ]);
+
+        let json = serde_json::to_string(&warnings).unwrap();
+        let decoded: WarningArray = serde_json::from_str(&json).unwrap();
+        let decoded = decoded.into_vec();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].warn_type, Warnings::OutdatedVersion);
+        assert_eq!(decoded[1].warn_mesg.as_deref(), Some("/tmp/lock"));
+
+        let legacy: WarningArray =
+            serde_json::from_str(r#"[{"warn_type":"Warning","warn_mesg":null}]"#).unwrap();
+        assert_eq!(legacy.into_vec()[0].created_at, 0);
+    }
+
+    #[test]
+    fn test_error_array_serialize_poisoned() {
+        let errors = ErrorArray::new(vec![ErrorArrayItem::new(Errors::Timeout, "socket")]);
+        let poisoner = errors.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.0.write().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+
+        let json = serde_json::to_string(&errors).unwrap();
+        let decoded: Vec<ErrorArrayItem> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].err_type, Errors::Timeout);
+        assert_eq!(decoded[0].err_mesg, "socket");
+    }
+
+    #[test]
+    fn test_error_array_into_exit_code() {
+        let errors = ErrorArray::new(vec![ErrorArrayItem::new(Errors::NotFound, "gone")]);
+        let handle = errors.clone();
+        assert_eq!(errors.into_exit_code(), std::process::ExitCode::FAILURE);
+        assert_eq!(handle.len(), 0);
+
+        assert_eq!(
+            ErrorArray::new_container().into_exit_code(),
+            std::process::ExitCode::SUCCESS
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "called `UnifiedResult::unwrap()` on an error: NotFound: gone")]
+    fn test_unified_result_unwrap_panics() {
+        let result: UnifiedResult<()> =
+            UnifiedResult::new(Err(ErrorArrayItem::new(Errors::NotFound, "gone")));
+        result.unwrap();
+    }
+
+    #[test]
+    fn test_serde_json_conversion_kinds() {
+        let broken = "{\n  \"name\": \"app\",\n  \"port\": ,\n}";
+        let err = serde_json::from_str::<serde_json::Value>(broken).unwrap_err();
+        let item = ErrorArrayItem::from(err);
+        assert_eq!(item.err_type, Errors::JsonReading);
+        assert!(item.err_mesg.contains("line 3"), "{}", item.err_mesg);
+        assert!(item.err_mesg.contains("column"), "{}", item.err_mesg);
+
+        let io_err = serde_json::Error::io(io::Error::other("disk"));
+        assert_eq!(ErrorArrayItem::from(io_err).err_type, Errors::InputOutput);
+    }
+
+    #[test]
+    fn test_serde_yaml_conversion_kind() {
+        let err =
+            serde_yaml::from_str::<serde_yaml::Value>("name: app\nports: [1, 2\n").unwrap_err();
+        let item = ErrorArrayItem::from(err);
+        assert_eq!(item.err_type, Errors::ConfigParsing);
+        assert!(item.err_mesg.contains("line"), "{}", item.err_mesg);
+    }
+
+    #[test]
+    fn test_serde_data_and_eof_errors_keep_classification() {
+        // Well formed JSON with the wrong shape is a Data error, truncated input is Eof
+        let data_err = serde_json::from_str::<Vec<u32>>("[1, \"two\"]").unwrap_err();
+        assert_eq!(data_err.classify(), serde_json::error::Category::Data);
+        let item = ErrorArrayItem::from(data_err);
+        assert_eq!(item.err_type, Errors::JsonReading);
+        assert!(item.err_mesg.contains("column"), "{}", item.err_mesg);
+
+        let eof_err = serde_json::from_str::<serde_json::Value>("{\"name\": ").unwrap_err();
+        assert_eq!(eof_err.classify(), serde_json::error::Category::Eof);
+        assert_eq!(ErrorArrayItem::from(eof_err).err_type, Errors::JsonReading);
+
+        let yaml_err = serde_yaml::from_str::<Vec<u32>>("- 1\n- two\n").unwrap_err();
+        let item = ErrorArrayItem::from(yaml_err);
+        assert_eq!(item.err_type, Errors::ConfigParsing);
+        assert!(item.err_mesg.contains("line 2"), "{}", item.err_mesg);
+    }
+
+    #[test]
+    fn test_arrays_recover_from_poisoned_lock() {
+        let mut errors = ErrorArray::new(vec![ErrorArrayItem::new(Errors::Timeout, "socket")]);
+        let mut warnings = WarningArray::new(vec![WarningArrayItem::new(Warnings::Warning)]);
+        let (error_poisoner, warning_poisoner) = (errors.clone(), warnings.clone());
+        let _ = std::thread::spawn(move || {
+            let _errors = error_poisoner.0.write().unwrap();
+            let _warnings = warning_poisoner.0.write().unwrap();
+            panic!("poison both locks");
+        })
+        .join();
+
+        assert_eq!(errors.try_len(), None);
+        assert_eq!(warnings.try_len(), None);
+
+        errors.push(ErrorArrayItem::new(Errors::NotFound, "gone"));
+        errors.append(ErrorArray::new(vec![ErrorArrayItem::new(
+            Errors::GeneralError,
+            "late",
+        )]));
+        warnings.push(WarningArrayItem::new(Warnings::OutdatedVersion));
+        assert_eq!(errors.len(), 3);
+        assert_eq!(warnings.len(), 2);
+
+        errors.display();
+        warnings.clone().display();
+        assert_eq!(errors.len(), 0);
+        assert_eq!(warnings.len(), 0);
+    }
+
+    #[test]
+    fn test_unified_result_inspect() {
+        let mut seen: Option<u32> = None;
+        let result: UnifiedResult<u32> = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+            5,
+            WarningArrayItem::new(Warnings::OutdatedVersion),
+        )));
+        let result = result
+            .inspect(|value| seen = Some(*value))
+            .inspect_err(|_| panic!("no error to inspect"));
+        assert_eq!(seen, Some(5));
+
+        let (data, warnings) = result.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(data, 5);
+        assert_eq!(warnings.len(), 1);
+
+        let mut kind: Option<Errors> = None;
+        let result: UnifiedResult<u32> =
+            UnifiedResult::new(Err(ErrorArrayItem::new(Errors::Timeout, "socket")));
+        let result = result
+            .inspect(|_| panic!("no value to inspect"))
+            .inspect_err(|e| kind = Some(e.err_type));
+        assert_eq!(kind, Some(Errors::Timeout));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_wrap_err_chain() {
+        fn open() -> Result<(), ErrorArrayItem> {
+            Err(ErrorArrayItem::new(
+                Errors::OpeningFile,
+                "No such file or directory",
+            ))
+        }
+
+        fn read_config() -> Result<(), ErrorArrayItem> {
+            open().wrap_err("reading /etc/app.conf")
+        }
+
+        fn start() -> Result<(), ErrorArrayItem> {
+            read_config().wrap_err("while loading config")
+        }
+
+        let err = start().wrap_err("starting service").unwrap_err();
+        assert_eq!(err.err_type, Errors::OpeningFile);
+        assert_eq!(
+            err.format_with(DisplayStyle::Plain),
+            "OpeningFile: starting service: while loading config: reading /etc/app.conf: No such file or directory"
+        );
+
+        let ok: Result<u8, ErrorArrayItem> = Ok(1);
+        assert_eq!(ok.wrap_err("unused").unwrap(), 1);
+
+        let item = ErrorArrayItem::new(Errors::OpeningFile, "No such file or directory")
+            .wrap("reading /etc/app.conf")
+            .wrap("while loading config");
+        assert_eq!(item.err_type, Errors::OpeningFile);
+        assert_eq!(
+            item.err_mesg.as_str(),
+            "while loading config: reading /etc/app.conf: No such file or directory"
+        );
+    }
+
+    // Deliberately exhaustive, adding an Errors variant fails to compile until the
+    // classification below is updated.
+    fn expected_classification(kind: Errors) -> (ErrorCategory, bool) {
+        match kind {
+            Errors::OpeningFile => (ErrorCategory::Filesystem, false),
+            Errors::ReadingFile => (ErrorCategory::Filesystem, false),
+            Errors::CreatingFile => (ErrorCategory::Filesystem, false),
+            Errors::DeletingFile => (ErrorCategory::Filesystem, false),
+            Errors::SettingPermissionsFile => (ErrorCategory::Filesystem, false),
+            Errors::UntaringFile => (ErrorCategory::Filesystem, false),
+            Errors::InvalidFile => (ErrorCategory::Filesystem, false),
+            Errors::CreatingDirectory => (ErrorCategory::Filesystem, false),
+            Errors::DeletingDirectory => (ErrorCategory::Filesystem, false),
+            Errors::SettingPermissionsDirectory => (ErrorCategory::Filesystem, false),
+            Errors::JsonCreation => (ErrorCategory::Data, false),
+            Errors::JsonReading => (ErrorCategory::Data, false),
+            Errors::InvalidType => (ErrorCategory::Data, false),
+            Errors::InvalidChunkData => (ErrorCategory::Data, false),
+            Errors::InvalidHMACData => (ErrorCategory::Data, false),
+            Errors::InvalidHMACSize => (ErrorCategory::Data, false),
+            Errors::InvalidKey => (ErrorCategory::Data, false),
+            Errors::InvalidHexData => (ErrorCategory::Data, false),
+            Errors::InvalidIvData => (ErrorCategory::Data, false),
+            Errors::InvalidBlockData => (ErrorCategory::Data, false),
+            Errors::InvalidAuthRequest => (ErrorCategory::Data, false),
+            Errors::InvalidMapRequest => (ErrorCategory::Data, false),
+            Errors::InvalidMapVersion => (ErrorCategory::Data, false),
+            Errors::InvalidMapData => (ErrorCategory::Data, false),
+            Errors::InvalidMapHash => (ErrorCategory::Data, false),
+            Errors::InvalidBufferFit => (ErrorCategory::Data, false),
+            Errors::InvalidUtf8Data => (ErrorCategory::Data, false),
+            Errors::InvalidSignature => (ErrorCategory::Data, false),
+            Errors::PermissionDenied => (ErrorCategory::Auth, false),
+            Errors::Unauthorized => (ErrorCategory::Auth, false),
+            Errors::NotFound => (ErrorCategory::Filesystem, false),
+            Errors::AlreadyExists => (ErrorCategory::Filesystem, false),
+            Errors::Network => (ErrorCategory::Network, true),
+            Errors::Protocol => (ErrorCategory::Network, false),
+            Errors::ConnectionError => (ErrorCategory::Network, true),
+            Errors::Timeout => (ErrorCategory::Network, true),
+            Errors::ConnectionTimedOut => (ErrorCategory::Network, true),
+            Errors::PortalNotFound => (ErrorCategory::Network, false),
+            Errors::PortalConnectionFailed => (ErrorCategory::Network, true),
+            Errors::AuthenticationError => (ErrorCategory::Auth, false),
+            Errors::IdentityError => (ErrorCategory::Auth, false),
+            Errors::IdentityInvalid => (ErrorCategory::Auth, false),
+            Errors::AppState => (ErrorCategory::Config, false),
+            Errors::ConfigReading => (ErrorCategory::Config, false),
+            Errors::ConfigParsing => (ErrorCategory::Config, false),
+            Errors::OutOfMemory => (ErrorCategory::Resource, false),
+            Errors::OverRamLimit => (ErrorCategory::Resource, false),
+            Errors::MessageDecode => (ErrorCategory::Data, false),
+            Errors::MessageEncode => (ErrorCategory::Data, false),
+            Errors::TimedOut => (ErrorCategory::Sync, true),
+            Errors::LockWithTimeoutRead => (ErrorCategory::Sync, true),
+            Errors::LockWithTimeoutWrite => (ErrorCategory::Sync, true),
+            Errors::SupervisedChild => (ErrorCategory::Process, false),
+            Errors::SupervisedChildDied => (ErrorCategory::Process, false),
+            Errors::SupervisedChildKilled => (ErrorCategory::Process, false),
+            Errors::SupervisedChildLost => (ErrorCategory::Process, false),
+            Errors::SupervisedChildFat => (ErrorCategory::Process, false),
+            Errors::InputOutput => (ErrorCategory::General, false),
+            Errors::GeneralError => (ErrorCategory::General, false),
+            Errors::InitializationError => (ErrorCategory::General, false),
+            Errors::SecretArray => (ErrorCategory::General, false),
+            Errors::Git => (ErrorCategory::General, false),
+            Errors::GitFileMissing => (ErrorCategory::General, false),
+            Errors::GitFileIllegible => (ErrorCategory::General, false),
+            Errors::ToggleControl => (ErrorCategory::General, false),
+            Errors::DEPSYSTEM => (ErrorCategory::General, false),
+            Errors::DEPLOGGER => (ErrorCategory::General, false),
+            Errors::DEPRECS => (ErrorCategory::General, false),
+        }
+    }
+
+    #[test]
+    fn test_error_classification() {
+        for kind in Errors::ALL.iter().copied() {
+            let (category, retryable) = expected_classification(kind);
+            assert_eq!(kind.category(), category, "{:?}", kind);
+            assert_eq!(kind.is_retryable(), retryable, "{:?}", kind);
+        }
+
+        let item = ErrorArrayItem::new(Errors::Timeout, "socket");
+        assert_eq!(item.category(), ErrorCategory::Network);
+        assert!(item.is_retryable());
+        assert!(!ErrorArrayItem::new(Errors::PermissionDenied, "/root").is_retryable());
+        assert!(!Errors::InvalidKey.is_retryable());
+    }
+
+    #[test]
+    fn test_collect_results_success_merges_warnings() {
+        let results: Vec<UnifiedResult<u32>> = vec![
+            UnifiedResult::new(Ok(1)),
+            UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+                2,
+                WarningArrayItem::new(Warnings::OutdatedVersion),
+            ))),
+            UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+                3,
+                WarningArrayItem::new(Warnings::MisAlignedChunk),
+            ))),
+        ];
+
+        let (values, warnings) = collect_results(results)
+            .into_result_with_warnings()
+            .unwrap()
+            .into_parts();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(warnings.len(), 2);
+
+        let collected: UnifiedResult<Vec<u32>> =
+            (0..3).map(|n| UnifiedResult::new(Ok(n))).collect();
+        match collected {
+            UnifiedResult::ResultNoWarns(Ok(values)) => assert_eq!(values, vec![0, 1, 2]),
+            _ => panic!("Expected ResultNoWarns(Ok)"),
+        }
+    }
+
+    #[test]
+    fn test_collect_results_merges_failures() {
+        let results: Vec<UnifiedResult<u32>> = vec![
+            UnifiedResult::new(Err(ErrorArrayItem::new(Errors::NotFound, "a.txt"))),
+            UnifiedResult::new(Ok(1)),
+            UnifiedResult::new(Err(ErrorArrayItem::new(Errors::PermissionDenied, "b.txt"))),
+        ];
+
+        let err = collect_results(results).get_err().unwrap();
+        assert_eq!(err.err_type, Errors::NotFound);
+        assert_eq!(
+            err.err_mesg,
+            "2 of 3 operations failed: NotFound: a.txt; PermissionDenied: b.txt"
+        );
+
+        let empty: UnifiedResult<Vec<u32>> = collect_results(Vec::new());
+        assert_eq!(empty.unwrap(), Vec::<u32>::new());
+    }
+
+    #[cfg(feature = "nightly-try")]
+    fn first_then_second(fail_second: bool) -> UnifiedResult<u32> {
+        let (first, mut warnings) = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+            1,
+            WarningArrayItem::new(Warnings::OutdatedVersion),
+        )))?
+        .into_parts();
+        let second: OkWarning<u32> = match fail_second {
+            true => UnifiedResult::new_warn(Err(ErrorArrayItem::new(Errors::NotFound, "gone")))?,
+            false => UnifiedResult::new(Ok(2))?,
+        };
+        warnings.append(second.warning);
+        UnifiedResult::new_warn(Ok(OkWarning {
+            data: first + second.data,
+            warning: warnings,
+        }))
+    }
+
+    #[cfg(feature = "nightly-try")]
+    #[test]
+    fn test_question_mark_on_unified_result() {
+        let (sum, warnings) = first_then_second(false)
+            .into_result_with_warnings()
+            .unwrap()
+            .into_parts();
+        assert_eq!(sum, 3);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings.contains_type(Warnings::OutdatedVersion));
+        match first_then_second(true) {
+            UnifiedResult::ResultNoWarns(Err(e)) => assert_eq!(e.err_type, Errors::NotFound),
+            _ => panic!("Expected ResultNoWarns(Err)"),
+        }
+    }
+
+    #[test]
+    fn test_ok_warning_map_and_add_warning() {
+        let ok = OkWarning::new_none("42")
+            .add_warning(WarningArrayItem::new(Warnings::OutdatedVersion))
+            .add_warning(WarningArrayItem::new(Warnings::MisAlignedChunk));
+        let warnings = ok.warning.clone();
+
+        let mapped: OkWarning<u32> = ok.map(|raw| raw.parse().unwrap());
+        assert_eq!(mapped.data, 42);
+        assert_eq!(mapped.warning.len(), 2);
+        assert_eq!(warnings.len(), 2);
+        assert!(mapped.warning.contains_type(Warnings::MisAlignedChunk));
+    }
+
+    #[test]
+    fn test_ok_warning_push_and_merge() {
+        let mut raw =
+            OkWarning::new_from_item("8080", WarningArrayItem::new(Warnings::OutdatedVersion));
+        raw.push_warning(WarningArrayItem::new(Warnings::ConnectionLost));
+
+        let port: OkWarning<u16> = raw
+            .map(|raw| raw.parse::<u32>().unwrap())
+            .map(|port| port as u16);
+        assert_eq!(port.warning.len(), 2);
+
+        let host = OkWarning::new_from_item("localhost", WarningArrayItem::new(Warnings::Warning));
+        let merged: OkWarning<(u16, &str)> = port.merge(host);
+        assert_eq!(merged.data, (8080, "localhost"));
+        assert_eq!(merged.warning.len(), 3);
+
+        let (data, warnings) = merged.into_parts();
+        assert_eq!(data.0, 8080);
+        let kinds: Vec<Warnings> = warnings
+            .into_vec()
+            .into_iter()
+            .map(|w| w.warn_type)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Warnings::OutdatedVersion,
+                Warnings::ConnectionLost,
+                Warnings::Warning
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ok_warning_merge_shared_array() {
+        let shared = WarningArray::new(vec![WarningArrayItem::new(Warnings::Warning)]);
+        let left = OkWarning {
+            data: 1,
+            warning: shared.clone(),
+        };
+        let right = OkWarning {
+            data: 2,
+            warning: shared,
+        };
+        let merged = left.merge(right);
+        assert_eq!(merged.data, (1, 2));
+        assert_eq!(merged.warning.len(), 1);
+    }
+
+    #[derive(Debug)]
+    struct LayeredError {
+        message: &'static str,
+        source: Option<Box<LayeredError>>,
+    }
+
+    impl std::fmt::Display for LayeredError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.message)
+        }
+    }
+
+    impl std::error::Error for LayeredError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.source
+                .as_deref()
+                .map(|err| err as &(dyn std::error::Error + 'static))
+        }
+    }
+
+    #[test]
+    fn test_from_dyn_error_chain() {
+        let chain = LayeredError {
+            message: "loading config",
+            source: Some(Box::new(LayeredError {
+                message: "reading /etc/app.conf",
+                source: Some(Box::new(LayeredError {
+                    message: "permission denied",
+                    source: None,
+                })),
+            })),
+        };
+
+        let item = ErrorArrayItem::from_dyn(Errors::ConfigReading, &chain);
+        assert_eq!(item.err_type, Errors::ConfigReading);
+        assert_eq!(
+            item.err_mesg,
+            "loading config: reading /etc/app.conf: permission denied"
+        );
+
+        let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(chain);
+        let item = ErrorArrayItem::from(boxed);
+        assert_eq!(item.err_type, Errors::GeneralError);
+        assert!(item.err_mesg.ends_with("permission denied"));
+    }
+
+    #[test]
+    fn test_from_err_and_display() {
+        let chain = LayeredError {
+            message: "parsing config",
+            source: Some(Box::new(LayeredError {
+                message: "unexpected token",
+                source: None,
+            })),
+        };
+        let result: Result<(), LayeredError> = Err(chain);
+
+        let item = result
+            .map_err(|e| ErrorArrayItem::from_err(Errors::ConfigParsing, e))
+            .unwrap_err();
+        assert_eq!(item.err_type, Errors::ConfigParsing);
+        assert_eq!(item.err_mesg, "parsing config: unexpected token");
+
+        let item = ErrorArrayItem::from_display(Errors::InvalidType, 'x');
+        assert_eq!(item.err_type, Errors::InvalidType);
+        assert_eq!(item.err_mesg, "x");
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_from_anyhow_error() {
+        let err = anyhow::anyhow!("permission denied").context("reading /etc/app.conf");
+        let item = ErrorArrayItem::from(err);
+        assert_eq!(item.err_mesg, "reading /etc/app.conf: permission denied");
+    }
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        let kinds = Errors::ALL.to_vec();
+        let mut codes: Vec<u16> = kinds.iter().map(|kind| kind.code()).collect();
+
+        for kind in &kinds {
+            assert_eq!(Errors::from_code(kind.code()), Some(*kind), "{:?}", kind);
+        }
+
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), kinds.len(), "error codes must be unique");
+
+        assert_eq!(Errors::OpeningFile.code(), 101);
+        assert_eq!(Errors::DEPRECS.code(), 9003);
+        assert_eq!(Errors::from_code(0), None);
+    }
+
+    #[test]
+    fn test_error_code_serialized() {
+        let err = ErrorArrayItem::new(Errors::NotFound, "gone");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["code"], Errors::NotFound.code());
+        assert_eq!(json["err_type"], "NotFound");
+        assert!(json.get("context").is_none());
+
+        let decoded: ErrorArrayItem = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, err);
+    }
+
+    #[tokio::test]
+    async fn test_from_join_error() {
+        let panicked = tokio::spawn(async { panic!("task blew up") })
+            .await
+            .unwrap_err();
+        let item = ErrorArrayItem::from(panicked);
+        assert_eq!(item.err_type, Errors::GeneralError);
+        assert!(item.err_mesg.contains("panicked"), "{}", item.err_mesg);
+
+        let handle = tokio::spawn(async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        });
+        handle.abort();
+        let cancelled = handle.await.unwrap_err();
+        let item = ErrorArrayItem::from(cancelled);
+        assert_eq!(item.err_type, Errors::SupervisedChildLost);
+    }
+
+    #[tokio::test]
+    async fn test_from_elapsed() {
+        let elapsed = tokio::time::timeout(
+            std::time::Duration::from_millis(1),
+            tokio::time::sleep(std::time::Duration::from_secs(60)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(ErrorArrayItem::from(elapsed).err_type, Errors::Timeout);
+    }
+
+    fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload
+                .downcast::<&str>()
+                .map(|message| message.to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn test_unified_result_expect() {
+        let ok: UnifiedResult<u32> = UnifiedResult::new(Ok(5));
+        assert_eq!(ok.expect("reading count"), 5);
+
+        let payload = std::panic::catch_unwind(|| {
+            let result: UnifiedResult<u32> =
+                UnifiedResult::new(Err(ErrorArrayItem::new(Errors::NotFound, "/etc/app.conf")));
+            result.expect("loading config")
+        })
+        .unwrap_err();
+        assert_eq!(
+            panic_message(payload),
+            "loading config: NotFound: /etc/app.conf"
+        );
+    }
+
+    #[test]
+    fn test_unified_result_expect_warn() {
+        let clean: UnifiedResult<u32> = UnifiedResult::new_warn(Ok(OkWarning::new_none(5)));
+        assert_eq!(clean.expect_warn("reading count"), 5);
+
+        let payload = std::panic::catch_unwind(|| {
+            let warned: UnifiedResult<u32> = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+                5,
+                WarningArrayItem::new_details(Warnings::OutdatedVersion, "v1".to_string()),
+            )));
+            warned.expect_warn("reading count")
+        })
+        .unwrap_err();
+        let message = panic_message(payload);
+        assert!(
+            message.starts_with("reading count: unexpected warnings:"),
+            "{}",
+            message
+        );
+        assert!(message.contains("v1"), "{}", message);
+
+        let payload = std::panic::catch_unwind(|| {
+            let failed: UnifiedResult<u32> =
+                UnifiedResult::new(Err(ErrorArrayItem::new(Errors::Timeout, "socket")));
+            failed.expect_warn("reading count")
+        })
+        .unwrap_err();
+        assert_eq!(panic_message(payload), "reading count: Timeout: socket");
+    }
+
+    #[test]
+    fn test_arrays_default_is_empty_and_clear() {
+        let mut errors = ErrorArray::default();
+        let mut warnings = WarningArray::default();
+        assert!(errors.is_empty());
+        assert!(warnings.is_empty());
+
+        errors.push(ErrorArrayItem::new(Errors::NotFound, "gone"));
+        warnings.push(WarningArrayItem::new(Warnings::Warning));
+        assert!(!errors.is_empty());
+        assert!(!warnings.is_empty());
+
+        errors.clear();
+        warnings.clear();
+        assert!(errors.is_empty());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_arrays_is_empty_under_concurrent_push() {
+        let errors = ErrorArray::default();
+        let warnings = WarningArray::default();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let mut errors = errors.clone();
+                let mut warnings = warnings.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        errors.push(ErrorArrayItem::new(
+                            Errors::Timeout,
+                            format!("worker {}", i),
+                        ));
+                        warnings.push(WarningArrayItem::new(Warnings::Warning));
+                        assert!(!errors.is_empty());
+                        assert!(!warnings.is_empty());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(errors.len(), 400);
+        assert_eq!(warnings.len(), 400);
+        warnings.clone().clear();
+        assert!(warnings.is_empty());
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn test_arrays_from_iterator_and_extend() {
+        let results: Vec<Result<u32, ErrorArrayItem>> = (0..1000)
+            .map(|i| match i % 2 {
+                0 => Ok(i),
+                _ => Err(ErrorArrayItem::new(
+                    Errors::ReadingFile,
+                    format!("file {}", i),
+                )),
+            })
+            .collect();
+        let mut errors: ErrorArray = results.into_iter().filter_map(Result::err).collect();
+        assert_eq!(errors.len(), 500);
+
+        errors.extend(
+            (1000..1500).map(|i| ErrorArrayItem::new(Errors::Timeout, format!("file {}", i))),
+        );
+        let items = errors.into_vec();
+        assert_eq!(items.len(), 1000);
+        assert_eq!(items[0].err_mesg, "file 1");
+        assert_eq!(items[499].err_mesg, "file 999");
+        assert_eq!(items[500].err_mesg, "file 1000");
+        assert_eq!(items[999].err_type, Errors::Timeout);
+
+        let mut warnings: WarningArray = (0..1000)
+            .map(|i| WarningArrayItem::new_details(Warnings::Warning, i.to_string()))
+            .collect();
+        warnings.extend(vec![WarningArrayItem::new(Warnings::OutdatedVersion)]);
+        let items = warnings.into_vec();
+        assert_eq!(items.len(), 1001);
+        assert!(items[..1000]
+            .iter()
+            .enumerate()
+            .all(|(i, item)| item.warn_mesg.as_deref() == Some(i.to_string().as_str())));
+        assert_eq!(items[1000].warn_type, Warnings::OutdatedVersion);
+    }
+
+    #[test]
+    fn test_extend_from_clone_does_not_deadlock() {
+        let mut errors = ErrorArray::new(vec![ErrorArrayItem::new(Errors::NotFound, "a")]);
+        let handle = errors.clone();
+        // The iterator reads the shared lock lazily while extend runs
+        errors.extend(
+            (0..2).map(|_| ErrorArrayItem::new(Errors::NotFound, handle.len().to_string())),
+        );
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors.into_vec()[2].err_mesg, "1");
+    }
+
+    #[test]
+    fn test_unified_result_warnings_accessors() {
+        let mut result: UnifiedResult<u32> = UnifiedResult::new_warn(Ok(OkWarning::new_from_item(
+            7,
+            WarningArrayItem::new(Warnings::OutdatedVersion),
+        )));
+        assert_eq!(result.warning_count(), 1);
+        for _ in 0..3 {
+            let warnings = result.warnings().unwrap();
+            assert!(warnings.contains_type(Warnings::OutdatedVersion));
+        }
+        assert_eq!(result.warning_count(), 1);
+
+        let taken = result.take_warnings();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(result.warning_count(), 0);
+        assert!(result.warnings().unwrap().is_empty());
+        assert_eq!(result.unwrap(), 7);
+
+        let mut plain: UnifiedResult<u32> = UnifiedResult::new(Ok(7));
+        assert!(plain.warnings().is_none());
+        assert_eq!(plain.warning_count(), 0);
+        assert!(plain.take_warnings().is_empty());
+
+        let failed: UnifiedResult<u32> =
+            UnifiedResult::new_warn(Err(ErrorArrayItem::new(Errors::Timeout, "socket")));
+        assert!(failed.warnings().is_none());
+    }
+
+    // With the backtrace feature and RUST_BACKTRACE set, `{:#}` ends with the backtrace
+    fn without_backtrace(rendered: String) -> String {
+        match rendered.split_once("\nbacktrace:\n") {
+            Some((head, _)) => head.to_string(),
+            None => rendered,
+        }
+    }
+
+    #[test]
+    fn test_error_item_alternate_display() {
+        let err = ErrorArrayItem::new(Errors::NotFound, "Missing")
+            .context("path", "/tmp/x")
+            .context("op", "read");
+        assert_eq!(
+            without_backtrace(format!("{:#}", err)),
+            "kind: NotFound\ncode: 503\nmessage: Missing\ncontext:\n  path: /tmp/x\n  op: read"
+        );
+
+        let bare = ErrorArrayItem::new(Errors::Timeout, "socket");
+        assert_eq!(
+            without_backtrace(format!("{:#}", bare)),
+            "kind: Timeout\ncode: 604\nmessage: socket"
+        );
+        assert_eq!(format!("{}", bare), bare.format_with(get_display_style()));
+    }
+
+    #[test]
+    fn test_error_item_to_log_line() {
+        let mut err = ErrorArrayItem::new(Errors::PermissionDenied, "line one\nline \\two\\")
+            .context("path", "/etc/shadow")
+            .context("note", "said \"no\"");
+        err.created_at = 42;
+        let line = err.to_log_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(
+            line,
+            r#"kind=PermissionDenied code=501 created_at=42 msg="line one\nline \\two\\" ctx.path="/etc/shadow" ctx.note="said \"no\"""#
+        );
+    }
+
+    #[test]
+    fn test_warning_dedup_with_count() {
+        let mut warnings = WarningArray::new_container();
+        for _ in 0..500 {
+            warnings.push(WarningArrayItem::new_details(
+                Warnings::ResourceExhaustion,
+                "disk nearly full".to_string(),
+            ));
+        }
+        warnings.push(WarningArrayItem::new(Warnings::OutdatedVersion));
+        warnings.dedup_with_count();
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings.count_by_type()[&Warnings::ResourceExhaustion], 500);
+
+        let output = warnings.clone().display_to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Warning: resource exhaustion - disk nearly full (x500)",
+                "Warning: outdated version",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warning_counts_survive_append() {
+        let collapsed = |n: usize| {
+            let mut warnings: WarningArray = (0..n)
+                .map(|_| WarningArrayItem::new(Warnings::ConnectionLost))
+                .collect();
+            warnings.dedup_with_count();
+            warnings
+        };
+
+        let mut first = collapsed(3);
+        first.append(collapsed(4));
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.count_by_type()[&Warnings::ConnectionLost], 7);
+
+        first.dedup_with_count();
+        let items = first.into_vec();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].count, 7);
+
+        let json = serde_json::to_value(&items[0]).unwrap();
+        assert_eq!(json["count"], 7);
+        let single = serde_json::to_value(WarningArrayItem::new(Warnings::Warning)).unwrap();
+        assert!(single.get("count").is_none());
+        let restored: WarningArrayItem = serde_json::from_value(single).unwrap();
+        assert_eq!(restored.count, 1);
+    }
+
+    fn all_warning_kinds() -> Vec<Warnings> {
+        vec![
+            Warnings::Warning,
+            Warnings::OutdatedVersion,
+            Warnings::MisAlignedChunk,
+            Warnings::FileNotDeleted,
+            Warnings::ConnectionLost,
+            Warnings::ResourceExhaustion,
+            Warnings::UnexpectedBehavior,
+            Warnings::UnexpectedConfiguration,
+        ]
+    }
+
+    #[test]
+    fn test_warnings_as_hashmap_key() {
+        let mut seen: std::collections::HashMap<Warnings, usize> = std::collections::HashMap::new();
+        for kind in all_warning_kinds().into_iter().chain(all_warning_kinds()) {
+            *seen.entry(kind).or_default() += 1;
+        }
+        assert_eq!(seen.len(), 8);
+        assert!(seen.values().all(|count| *count == 2));
+
+        let mut sorted = all_warning_kinds();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, all_warning_kinds());
+    }
+
+    #[test]
+    fn test_warnings_display() {
+        let texts: Vec<String> = all_warning_kinds().iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "generic warning",
+                "outdated version",
+                "misaligned chunk",
+                "file not deleted",
+                "connection lost",
+                "resource exhaustion",
+                "unexpected behavior",
+                "unexpected configuration",
+            ]
+        );
+        assert_eq!(
+            WarningArrayItem::new_details(Warnings::FileNotDeleted, "/tmp/x".to_string())
+                .to_string(),
+            "Warning: file not deleted - /tmp/x"
+        );
+    }
+
+    #[test]
+    fn test_warnings_severity_follows_weight() {
+        assert_eq!(
+            Warnings::ResourceExhaustion.severity(),
+            WarningSeverity::Major
+        );
+        assert_eq!(
+            Warnings::UnexpectedConfiguration.severity(),
+            WarningSeverity::Minor
+        );
+        assert_eq!(Warnings::Warning.severity(), WarningSeverity::Info);
+        assert_eq!(WarningSeverity::Major.to_string(), "major");
+
+        for a in all_warning_kinds() {
+            for b in all_warning_kinds() {
+                if a.weight() > b.weight() {
+                    assert!(a.severity() >= b.severity(), "{:?} vs {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    fn mixed_errors() -> ErrorArray {
+        let mut errors = ErrorArray::new_container();
+        errors.push(ErrorArrayItem::new(Errors::Timeout, "db"));
+        for i in 0..5 {
+            errors.push(ErrorArrayItem::new(Errors::NotFound, format!("file {}", i)));
+        }
+        errors.push(ErrorArrayItem::new(Errors::PermissionDenied, "/etc/shadow"));
+        errors.push(ErrorArrayItem::new(Errors::Timeout, "cache"));
+        errors
+    }
+
+    #[test]
+    fn test_error_array_report_groups_by_count() {
+        let errors = mixed_errors();
+        let report = errors.report();
+        assert_eq!(
+            report,
+            [
+                "8 errors of 3 kinds",
+                "NotFound x5",
+                "  - file 0",
+                "  - file 1",
+                "  - file 2",
+                "  ... and 2 more",
+                "Timeout x2",
+                "  - db",
+                "  - cache",
+                "PermissionDenied x1",
+                "  - /etc/shadow",
+            ]
+            .join("\n")
+        );
+        assert_eq!(errors.len(), 8);
+        assert_eq!(format!("{:#}", errors), report.to_string());
+        assert_eq!(errors.len(), 8);
+    }
+
+    #[test]
+    fn test_error_array_report_limits() {
+        let errors = mixed_errors();
+        let report = errors.report_with(0, None);
+        assert!(report.starts_with("8 errors of 3 kinds\nNotFound x5\n  ... and 5 more\n"));
+
+        let capped = errors.report_with(3, Some(40));
+        assert!(capped.len() <= 40 + "... truncated".len());
+        assert_eq!(capped, "8 errors of 3 kinds\nNotFound x5\n... truncated");
+
+        assert_eq!(ErrorArray::default().report(), "0 errors of 0 kinds");
+    }
+
+    #[cfg(feature = "backtrace")]
+    #[test]
+    fn test_backtrace_captured() {
+        let err = ErrorArrayItem::new(Errors::GeneralError, "deep failure");
+        let clone = err.clone();
+        assert_eq!(err, clone);
+
+        // Capturing follows RUST_BACKTRACE, which the test environment may leave unset
+        match std::backtrace::Backtrace::capture().status() {
+            std::backtrace::BacktraceStatus::Captured => {
+                let rendered = err.backtrace().unwrap().to_string();
+                assert!(rendered.contains("test_backtrace_captured"), "{}", rendered);
+                assert!(format!("{:#}", err).contains("backtrace:"));
+                assert!(!format!("{}", err).contains("backtrace:"));
+            }
+            _ => assert!(err.backtrace().is_none()),
+        }
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert!(json.get("backtrace").is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_async_error_array_concurrent_push() {
+        let errors = AsyncErrorArray::new_container();
+        assert!(errors.is_empty().await);
+
+        let tasks: Vec<_> = (0..50)
+            .map(|i| {
+                let errors = errors.clone();
+                tokio::spawn(async move {
+                    errors
+                        .push(ErrorArrayItem::new(Errors::Timeout, format!("task {}", i)))
+                        .await;
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(errors.len().await, 50);
+        let sync = errors.clone().into_error_array().await;
+        assert_eq!(sync.len(), 50);
+        assert!(errors.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_async_error_array_conversions() {
+        let sync = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::NotFound, "a"),
+            ErrorArrayItem::new(Errors::NotFound, "b"),
+        ]);
+        let handle = sync.clone();
+
+        let errors = AsyncErrorArray::from(sync);
+        assert_eq!(errors.len().await, 2);
+        assert!(handle.is_empty());
+
+        errors.display().await;
+        assert!(errors.is_empty().await);
+        assert!(errors.into_error_array().await.is_empty());
+    }
+
+    #[test]
+    fn test_arrays_drain_and_take_across_clones() {
+        let mut errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::NotFound, "a"),
+            ErrorArrayItem::new(Errors::Timeout, "b"),
+        ]);
+        let handle = errors.clone();
+
+        let drained = errors.drain();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[1].err_mesg, "b");
+        assert!(handle.is_empty());
+
+        handle
+            .clone()
+            .push(ErrorArrayItem::new(Errors::NotFound, "c"));
+        let taken = errors.take();
+        assert_eq!(taken.len(), 1);
+        assert!(errors.is_empty());
+        assert!(handle.is_empty());
+        // The taken array no longer shares the lock
+        errors.push(ErrorArrayItem::new(Errors::NotFound, "d"));
+        assert_eq!(taken.len(), 1);
+        assert_eq!(handle.len(), 1);
+
+        let mut warnings = WarningArray::new(vec![WarningArrayItem::new(Warnings::Warning)]);
+        let shared = warnings.clone();
+        assert_eq!(warnings.take().len(), 1);
+        assert!(shared.is_empty());
+        shared
+            .clone()
+            .push(WarningArrayItem::new(Warnings::ConnectionLost));
+        assert_eq!(warnings.drain()[0].warn_type, Warnings::ConnectionLost);
+        assert!(shared.is_empty());
+    }
+
+    #[test]
+    fn test_warning_message_is_stringy() {
+        let warning = WarningArrayItem::new_details(Warnings::OutdatedVersion, "v1.2");
+        assert_eq!(warning.warn_mesg, Some(Stringy::from("v1.2")));
+        let owned = WarningArrayItem::new_details(Warnings::OutdatedVersion, String::from("v1.2"));
+        assert_eq!(owned.warn_mesg.unwrap(), "v1.2");
+
+        let json = serde_json::to_value(&warning).unwrap();
+        assert_eq!(json["warn_mesg"], "v1.2");
+        let decoded: WarningArrayItem = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.warn_mesg.unwrap(), "v1.2");
+    }
+
+    #[test]
+    fn test_error_downgraded_to_warning() {
+        let mut err = ErrorArrayItem::new(Errors::ConnectionTimedOut, "db").context("attempt", 3);
+        err.created_at = 99;
+        let warning = WarningArrayItem::from(err);
+        assert_eq!(warning.warn_type, Warnings::ConnectionLost);
+        assert_eq!(
+            warning.warn_mesg.unwrap(),
+            "ConnectionTimedOut: db [attempt=3]"
+        );
+        assert_eq!(warning.created_at, 99);
+
+        let kinds = [
+            (Errors::DeletingFile, Warnings::FileNotDeleted),
+            (Errors::OverRamLimit, Warnings::ResourceExhaustion),
+            (Errors::ConfigParsing, Warnings::UnexpectedConfiguration),
+            (Errors::NotFound, Warnings::UnexpectedBehavior),
+        ];
+        for (kind, expected) in kinds {
+            let warning: WarningArrayItem = ErrorArrayItem::new(kind, "x").into();
+            assert_eq!(warning.warn_type, expected, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_unified_unwrap_or_variants() {
+        let failed = || ErrorArrayItem::new(Errors::NotFound, "missing");
+
+        assert_eq!(UnifiedResult::new(Ok(5)).unwrap_or(1), 5);
+        assert_eq!(UnifiedResult::<u32>::new(Err(failed())).unwrap_or(1), 1);
+        assert_eq!(
+            UnifiedResult::<u32>::new_warn(Err(failed())).unwrap_or(1),
+            1
+        );
+
+        let kind: Errors =
+            UnifiedResult::new(Err(failed())).unwrap_or_else(|err: ErrorArrayItem| err.err_type);
+        assert_eq!(kind, Errors::NotFound);
+        assert_eq!(
+            UnifiedResult::new_warn(Ok(OkWarning::new_none(Errors::Timeout)))
+                .unwrap_or_else(|err: ErrorArrayItem| err.err_type),
+            Errors::Timeout
+        );
+
+        assert_eq!(
+            UnifiedResult::<Vec<u8>>::new(Err(failed())).unwrap_or_default(),
+            Vec::<u8>::new()
+        );
+        assert_eq!(
+            UnifiedResult::new_warn(Ok(OkWarning::new_none(7u32))).unwrap_or_default(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_unified_unwrap_or_displays_warnings_once() {
+        let warned = |value: u32| {
+            let warnings =
+                WarningArray::new(vec![WarningArrayItem::new(Warnings::OutdatedVersion)]);
+            let result = UnifiedResult::new_warn(Ok(OkWarning {
+                data: value,
+                warning: warnings.clone(),
+            }));
+            (result, warnings)
+        };
+
+        // Displaying drains the shared array, so a second display would have nothing left
+        let (result, warnings) = warned(3);
+        assert_eq!(result.unwrap_or(0), 3);
+        assert!(warnings.is_empty());
+
+        let (result, warnings) = warned(4);
+        assert_eq!(result.unwrap_or_else(|_| 0), 4);
+        assert!(warnings.is_empty());
+
+        let (result, warnings) = warned(5);
+        assert_eq!(result.unwrap_or_default(), 5);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_and_then_uf_accumulates_warnings() {
+        let first = WarningArrayItem::new_details(Warnings::OutdatedVersion, "make_dir");
+        let last = WarningArrayItem::new_details(Warnings::UnexpectedBehavior, "set_owner");
+        let created = WarningArray::new(vec![first.clone()]);
+
+        let result = UnifiedResult::new_warn(Ok(OkWarning {
+            data: 1u32,
+            warning: created.clone(),
+        }))
+        .and_then_uf(|step| UnifiedResult::new(Ok(step + 1)))
+        .and_then_uf(|step| {
+            UnifiedResult::new_warn(Ok(OkWarning::new_from_item(step + 1, last.clone())))
+        });
+
+        // Nothing was displayed along the way
+        assert_eq!(created.len(), 2);
+        let (steps, warnings) = result.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(steps, 3);
+        let messages: Vec<Option<Stringy>> = warnings
+            .into_vec()
+            .into_iter()
+            .map(|warning| warning.warn_mesg)
+            .collect();
+        assert_eq!(messages, vec![first.warn_mesg, last.warn_mesg]);
+
+        let failed: UnifiedResult<u32> = UnifiedResult::new(Ok(1u32))
+            .and_then_uf(|_| {
+                UnifiedResult::<u32>::new(Err(ErrorArrayItem::new(
+                    Errors::PermissionDenied,
+                    "chmod",
+                )))
+            })
+            .and_then_uf(|_| panic!("chain should stop at the first error"));
+        assert_eq!(failed.get_err().unwrap().err_type, Errors::PermissionDenied);
+    }
+
+    #[test]
+    fn test_and_then_with_shared_warning_array() {
+        // Both stages report into the same sink, appending it to itself must not deadlock
+        let sink = WarningArray::new(vec![WarningArrayItem::new(Warnings::OutdatedVersion)]);
+        let stage_sink = sink.clone();
+
+        let result = UnifiedResult::new_warn(Ok(OkWarning {
+            data: 1u32,
+            warning: sink.clone(),
+        }))
+        .and_then(move |step| {
+            UnifiedResult::new_warn(Ok(OkWarning {
+                data: step + 1,
+                warning: stage_sink,
+            }))
+        });
+
+        let (step, warnings) = result.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(step, 2);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(sink.len(), 1);
+    }
+
+    #[test]
+    fn test_std_parse_conversion_kinds() {
+        let mut float_err = "1.2.3".parse::<f64>().unwrap_err();
+        assert_eq!(
+            ErrorArrayItem::from(&mut float_err).err_type,
+            Errors::InvalidType
+        );
+        let item = ErrorArrayItem::from(float_err);
+        assert_eq!(item.err_type, Errors::InvalidType);
+        assert_eq!(item.err_mesg.as_str(), "invalid float literal");
+
+        let bytes: &[u8] = &[1, 2, 3];
+        let mut slice_err = <[u8; 4]>::try_from(bytes).unwrap_err();
+        assert_eq!(
+            ErrorArrayItem::from(&mut slice_err).err_type,
+            Errors::InvalidType
+        );
+        assert_eq!(
+            ErrorArrayItem::from(slice_err).err_type,
+            Errors::InvalidType
+        );
+
+        let mut char_err = char::try_from(0xD800u32).unwrap_err();
+        assert_eq!(
+            ErrorArrayItem::from(&mut char_err).err_type,
+            Errors::InvalidType
+        );
+        assert_eq!(ErrorArrayItem::from(char_err).err_type, Errors::InvalidType);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_os_string_conversion() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = OsString::from_vec(vec![b'a', 0xff, b'b']);
+        let mut returned = invalid.into_string().unwrap_err();
+        let item = ErrorArrayItem::from(&mut returned);
+        assert_eq!(item.err_type, Errors::InvalidUtf8Data);
+        assert_eq!(item.err_mesg.as_str(), "not valid unicode: a\u{fffd}b");
+
+        let converted: Result<String, ErrorArrayItem> =
+            returned.into_string().map_err(ErrorArrayItem::from);
+        assert_eq!(converted.unwrap_err().err_mesg, item.err_mesg);
+    }
+
+    #[test]
+    fn test_result_ext_err_kind_and_context() {
+        let parsed: Result<u16, ErrorArrayItem> =
+            "80a".parse::<u16>().err_kind(Errors::ConfigParsing);
+        let err = parsed.unwrap_err();
+        assert_eq!(err.err_type, Errors::ConfigParsing);
+        assert_eq!(err.err_mesg, "invalid digit found in string");
+
+        let read = std::fs::read("/definitely/missing/file")
+            .err_context(Errors::ReadingFile, "loading /definitely/missing/file");
+        let err = read.unwrap_err();
+        assert_eq!(err.err_type, Errors::ReadingFile);
+        assert!(
+            err.err_mesg
+                .starts_with("loading /definitely/missing/file: "),
+            "{}",
+            err.err_mesg
+        );
+
+        assert_eq!(
+            Ok::<u8, String>(1).err_kind(Errors::GeneralError).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_result_ext_wrap_err_converts() {
+        // wrap_err goes through the From impl, so io kinds are kept
+        let err = std::fs::read("/definitely/missing/file")
+            .wrap_err("loading cache")
+            .unwrap_err();
+        assert_eq!(err.err_type, Errors::NotFound);
+        assert!(
+            err.err_mesg.starts_with("loading cache: "),
+            "{}",
+            err.err_mesg
+        );
+    }
+
+    #[test]
+    fn test_errors_all_is_complete() {
+        // code() is exhaustive, so any kind from_code knows about must be listed in ALL
+        let known: Vec<Errors> = (0..=u16::MAX).filter_map(Errors::from_code).collect();
+        let mut listed: Vec<Errors> = Errors::ALL.to_vec();
+        listed.sort_by_key(Errors::code);
+        assert_eq!(known, listed);
+    }
+
+    #[test]
+    fn test_errors_name_round_trip() {
+        for kind in Errors::ALL {
+            assert_eq!(kind.as_str(), format!("{:?}", kind));
+            assert_eq!(kind.as_str().parse::<Errors>().unwrap(), *kind);
+            assert_eq!(
+                kind.as_str().to_lowercase().parse::<Errors>().unwrap(),
+                *kind
+            );
+            assert_eq!(
+                kind.as_str().to_uppercase().parse::<Errors>().unwrap(),
+                *kind
+            );
+        }
+
+        let err = "NoSuchKind".parse::<Errors>().unwrap_err();
+        assert_eq!(err.err_type, Errors::InvalidType);
+        assert!(err.err_mesg.contains("'NoSuchKind'"), "{}", err.err_mesg);
+    }
+
+    #[test]
+    fn test_array_display_leaves_items() {
+        let first = ErrorArrayItem::new(Errors::OpeningFile, "Failed to open file");
+        let second = ErrorArrayItem::new(Errors::Timeout, "socket");
+        let errors = ErrorArray::new(vec![first.clone(), second.clone()]);
+
+        let body = format!("errors:\n{}", errors);
+        assert_eq!(body, format!("errors:\n{}\n{}", first, second));
+        assert_eq!(
+            errors.to_string_vec(),
+            vec![first.to_string(), second.to_string()]
+        );
+        assert_eq!(errors.len(), 2);
+
+        let warning = WarningArrayItem::new_details(Warnings::OutdatedVersion, "v1");
+        let warnings = WarningArray::new(vec![warning.clone(), warning.clone()]);
+        assert_eq!(warnings.to_string(), format!("{}\n{}", warning, warning));
+        assert_eq!(warnings.to_string_vec().len(), 2);
+        assert_eq!(warnings.len(), 2);
+
+        assert_eq!(ErrorArray::new_container().to_string(), "");
+        assert_eq!(WarningArray::new_container().to_string(), "");
+    }
+
+    #[test]
+    fn test_array_display_methods_still_clear() {
+        let errors = ErrorArray::new(vec![ErrorArrayItem::new(Errors::NotFound, "a")]);
+        errors.display();
+        assert!(errors.is_empty());
+
+        let warnings = WarningArray::new(vec![WarningArrayItem::new(Warnings::Warning)]);
+        warnings.clone().display();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_warning_error_kind_mapping() {
+        let table = [
+            (Warnings::Warning, Errors::GeneralError),
+            (Warnings::OutdatedVersion, Errors::AppState),
+            (Warnings::MisAlignedChunk, Errors::InvalidChunkData),
+            (Warnings::FileNotDeleted, Errors::DeletingFile),
+            (Warnings::ConnectionLost, Errors::ConnectionError),
+            (Warnings::ResourceExhaustion, Errors::OverRamLimit),
+            (Warnings::UnexpectedBehavior, Errors::GeneralError),
+            (Warnings::UnexpectedConfiguration, Errors::ConfigParsing),
+        ];
+        assert_eq!(table.len(), all_warning_kinds().len());
+
+        for (warning, error) in table {
+            assert_eq!(warning.error_kind(), error, "{:?}", warning);
+        }
+
+        // Kinds with a close error counterpart come back unchanged
+        for kind in [
+            Warnings::FileNotDeleted,
+            Warnings::ConnectionLost,
+            Warnings::ResourceExhaustion,
+            Warnings::UnexpectedBehavior,
+            Warnings::UnexpectedConfiguration,
+        ] {
+            let item = ErrorArrayItem::from(WarningArrayItem::new(kind));
+            assert_eq!(WarningArrayItem::from(item).warn_type, kind);
+        }
+    }
+
+    #[test]
+    fn test_warning_to_error_message() {
+        let mut collapsed = WarningArrayItem::new_details(Warnings::ConnectionLost, "db");
+        collapsed.count = 3;
+        collapsed.created_at = 42;
+
+        let err = ErrorArrayItem::from(collapsed);
+        assert_eq!(err.err_type, Errors::ConnectionError);
+        assert_eq!(err.err_mesg, "db (x3)");
+        assert_eq!(err.created_at, 42);
+
+        let err = ErrorArrayItem::from(WarningArrayItem::new(Warnings::MisAlignedChunk));
+        assert_eq!(err.err_mesg, "misaligned chunk");
+    }
+
+    #[test]
+    fn test_warning_array_escalate() {
+        let warnings = WarningArray::new(vec![
+            WarningArrayItem::new_details(Warnings::ResourceExhaustion, "disk"),
+            WarningArrayItem::new_details(Warnings::OutdatedVersion, "v1"),
+            WarningArrayItem::new_details(Warnings::ResourceExhaustion, "memory"),
+        ]);
+
+        let (kept, errors) =
+            warnings.escalate(|item| item.warn_type == Warnings::ResourceExhaustion);
+        assert_eq!(kept.len(), 1);
+        assert!(kept.contains_type(Warnings::OutdatedVersion));
+        let messages: Vec<Stringy> = errors.into_vec().into_iter().map(|e| e.err_mesg).collect();
+        assert_eq!(messages, vec!["disk", "memory"]);
+    }
+
+    #[test]
+    fn test_ok_warning_fail_if() {
+        let build = || OkWarning {
+            data: 7u8,
+            warning: WarningArray::new(vec![
+                WarningArrayItem::new_details(Warnings::FileNotDeleted, "/tmp/a"),
+                WarningArrayItem::new(Warnings::OutdatedVersion),
+            ]),
+        };
+
+        let passed = build().fail_if(|item| item.warn_type == Warnings::ConnectionLost);
+        let (data, warnings) = passed.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(data, 7);
+        assert_eq!(warnings.len(), 2);
+
+        let failed = build().fail_if(|item| item.warn_type == Warnings::FileNotDeleted);
+        let err = failed.get_err().unwrap();
+        assert_eq!(err.err_type, Errors::DeletingFile);
+        assert_eq!(err.err_mesg, "/tmp/a");
+
+        let failed = build().fail_if(|_| true);
+        let err = failed.get_err().unwrap();
+        assert_eq!(err.err_type, Errors::DeletingFile);
+        assert_eq!(
+            err.err_mesg,
+            "2 warnings escalated: /tmp/a; outdated version"
+        );
+    }
+
+    fn fill_bounded(policy: OverflowPolicy, pushes: usize) -> ErrorArray {
+        let mut errors = ErrorArray::with_capacity_limit(3, policy);
+        for i in 0..pushes {
+            let kind = if i % 2 == 0 {
+                Errors::NotFound
+            } else {
+                Errors::Timeout
+            };
+            errors.push(ErrorArrayItem::new(kind, format!("e{}", i)));
+        }
+        errors
+    }
+
+    fn messages(items: &[ErrorArrayItem]) -> Vec<&str> {
+        items.iter().map(|item| item.err_mesg.as_str()).collect()
+    }
+
+    #[test]
+    fn test_bounded_error_array_at_limit() {
+        for policy in [
+            OverflowPolicy::DropOldest,
+            OverflowPolicy::DropNewest,
+            OverflowPolicy::CollapseIntoCount,
+        ] {
+            let errors = fill_bounded(policy, 3);
+            assert!(errors.overflow_notice().is_none(), "{:?}", policy);
+            assert_eq!(messages(&errors.into_vec()), vec!["e0", "e1", "e2"]);
+        }
+    }
+
+    #[test]
+    fn test_bounded_error_array_drop_oldest() {
+        let errors = fill_bounded(OverflowPolicy::DropOldest, 4);
+        assert_eq!(errors.len(), 4);
+        assert_eq!(
+            messages(&errors.into_vec()),
+            vec!["e1", "e2", "e3", "1 errors discarded, limit is 3"]
+        );
+
+        let items = fill_bounded(OverflowPolicy::DropOldest, 6).into_vec();
+        assert_eq!(messages(&items)[..3], ["e3", "e4", "e5"]);
+        let notice = &items[3];
+        assert_eq!(notice.err_type, Errors::GeneralError);
+        assert_eq!(notice.context, vec![("discarded".into(), "3".into())]);
+    }
+
+    #[test]
+    fn test_bounded_error_array_drop_newest() {
+        let items = fill_bounded(OverflowPolicy::DropNewest, 4).into_vec();
+        assert_eq!(
+            messages(&items),
+            vec!["e0", "e1", "e2", "1 errors discarded, limit is 3"]
+        );
+
+        let items = fill_bounded(OverflowPolicy::DropNewest, 6).into_vec();
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[3].context, vec![("discarded".into(), "3".into())]);
+    }
+
+    #[test]
+    fn test_bounded_error_array_collapse_into_count() {
+        let items = fill_bounded(OverflowPolicy::CollapseIntoCount, 8).into_vec();
+        assert_eq!(messages(&items)[..3], ["e0", "e1", "e2"]);
+        assert_eq!(items[3].err_mesg, "5 errors discarded, limit is 3");
+        assert_eq!(
+            items[3].context,
+            vec![
+                ("discarded".into(), "5".into()),
+                ("Timeout".into(), "3".into()),
+                ("NotFound".into(), "2".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bounded_error_array_notice_in_output() {
+        let errors = fill_bounded(OverflowPolicy::DropNewest, 4);
+        let notice = "GeneralError: 1 errors discarded, limit is 3 [discarded=1]";
+
+        assert_eq!(errors.to_string_vec()[3], notice);
+        let json = serde_json::to_string(&errors).unwrap();
+        let decoded: Vec<ErrorArrayItem> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.len(), 4);
+
+        let text = errors.clone().display_to_string();
+        assert_eq!(text.lines().last(), Some(notice));
+        // Moving the errors out resets the count
+        assert_eq!(errors.discarded(), 0);
+        assert!(errors.is_empty());
+
+        let errors = fill_bounded(OverflowPolicy::DropNewest, 5);
+        errors.display();
+        assert_eq!(errors.discarded(), 0);
+        assert!(errors.overflow_notice().is_none());
+        assert_eq!(errors.to_string(), "");
+    }
+
+    #[test]
+    fn test_bounded_error_array_append_and_room() {
+        let mut errors = ErrorArray::with_capacity_limit(2, OverflowPolicy::DropNewest);
+        errors.append(ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::NotFound, "a"),
+            ErrorArrayItem::new(Errors::NotFound, "b"),
+            ErrorArrayItem::new(Errors::NotFound, "c"),
+        ]));
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors.discarded(), 1);
+
+        // Clones share the limit, and new errors go in ahead of the notice
+        let mut shared = errors.clone();
+        shared.retain(|item| item.err_mesg != "b");
+        shared.push(ErrorArrayItem::new(Errors::Timeout, "d"));
+        shared.push(ErrorArrayItem::new(Errors::Timeout, "e"));
+        assert_eq!(errors.discarded(), 2);
+
+        // take keeps the limit and the count
+        let mut taken = errors.take();
+        assert_eq!(errors.discarded(), 0);
+        taken.push(ErrorArrayItem::new(Errors::Timeout, "f"));
+        assert_eq!(
+            messages(&taken.into_vec()),
+            vec!["a", "d", "3 errors discarded, limit is 2"]
+        );
+
+        let mut unbounded = ErrorArray::new_container();
+        unbounded.extend((0..100).map(|i| ErrorArrayItem::new(Errors::NotFound, i.to_string())));
+        assert_eq!(unbounded.len(), 100);
+    }
+
+    // The only test touching the global collector, others would race with its drain
+    #[test]
+    fn test_global_error_sink() {
+        let workers: Vec<_> = (0..3)
+            .map(|worker| {
+                std::thread::spawn(move || {
+                    for i in 0..10 {
+                        ErrorArrayItem::new(Errors::GeneralError, format!("{}-{}", worker, i))
+                            .report();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert_eq!(global().len(), 30);
+        let mut reported: Vec<String> = drain_global()
+            .into_iter()
+            .map(|err| err.err_mesg.to_string())
+            .collect();
+        reported.sort();
+        reported.dedup();
+        assert_eq!(reported.len(), 30);
+
+        assert!(global().is_empty());
+        assert!(drain_global().is_empty());
+    }
+
+    #[test]
+    fn test_unified_result_wire_ok() {
+        let sent: UnifiedResult<Vec<u16>> = UnifiedResult::new(Ok(vec![80, 443]));
+        let json = serde_json::to_value(&sent).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "status": "ok", "data": [80, 443] })
+        );
+
+        let received: UnifiedResult<Vec<u16>> = serde_json::from_value(json).unwrap();
+        assert!(
+            matches!(&received, UnifiedResult::ResultNoWarns(Ok(ports)) if ports == &[80, 443])
+        );
+    }
+
+    #[test]
+    fn test_unified_result_wire_ok_warn() {
+        let warning = WarningArrayItem::new_details(Warnings::OutdatedVersion, "v1");
+        let sent =
+            UnifiedResult::new_warn(Ok(OkWarning::new_from_item(String::from("up"), warning)));
+        let json = serde_json::to_string(&sent).unwrap();
+
+        let received: UnifiedResult<String> = serde_json::from_str(&json).unwrap();
+        let (data, warnings) = received.uf_unwrap_with_warnings().unwrap().strip_with();
+        assert_eq!(data, "up");
+        let warnings = warnings.into_vec();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].warn_type, Warnings::OutdatedVersion);
+        assert_eq!(warnings[0].warn_mesg.as_deref(), Some("v1"));
+
+        // An empty warning list keeps the ok_warn shape
+        let empty: UnifiedResult<u8> = UnifiedResult::new_warn(Ok(OkWarning::new_none(1)));
+        let json = serde_json::to_value(&empty).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "status": "ok_warn", "data": 1, "warnings": [] })
+        );
+        let received: UnifiedResult<u8> = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            &received,
+            UnifiedResult::ResultWarning(Ok(ok)) if ok.data == 1 && ok.warning.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_unified_result_wire_err() {
+        let error = ErrorArrayItem::new(Errors::NotFound, "/etc/app.conf");
+        for sent in [
+            UnifiedResult::<u8>::new(Err(error.clone())),
+            UnifiedResult::<u8>::new_warn(Err(error.clone())),
+        ] {
+            let json = serde_json::to_value(&sent).unwrap();
+            assert_eq!(json["status"], "err");
+
+            let received: UnifiedResult<u8> = serde_json::from_value(json).unwrap();
+            assert!(matches!(&received, UnifiedResult::ResultNoWarns(Err(_))));
+            assert_eq!(received.get_err().unwrap(), error);
+        }
+    }
+
+    #[test]
+    fn test_ok_warning_serializes_warnings_as_list() {
+        let ok = OkWarning::new_from_item(3u8, WarningArrayItem::new(Warnings::Warning));
+        let json = serde_json::to_value(&ok).unwrap();
+        assert_eq!(json["data"], 3);
+        assert!(json["warnings"].is_array());
+        assert_eq!(json["warnings"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_error_same_kind() {
+        let missing = ErrorArrayItem::new(Errors::NotFound, "a.txt");
+        let also_missing = ErrorArrayItem::new(Errors::NotFound, "b.txt");
+        let slow = ErrorArrayItem::new(Errors::Timeout, "a.txt");
+
+        assert!(missing.same_kind(&also_missing));
+        assert_ne!(missing, also_missing);
+        assert!(!missing.same_kind(&slow));
+        assert_eq!(KindKey(missing.clone()), KindKey(also_missing));
+        assert_ne!(KindKey(missing), KindKey(slow));
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)] // the backtrace is mutable, but KindKey only hashes err_type
+    fn test_kind_key_aggregation() {
+        let errors = mixed_errors();
+        let counts = errors.counts_by_kind();
+        assert_eq!(counts, errors.count_by_type());
+
+        let mut by_kind: std::collections::HashMap<KindKey, usize> =
+            std::collections::HashMap::new();
+        for item in errors.into_vec() {
+            *by_kind.entry(KindKey::from(item)).or_insert(0) += 1;
+        }
+
+        assert_eq!(by_kind.len(), counts.len());
+        for (key, count) in &by_kind {
+            assert_eq!(counts[&key.0.err_type], *count);
+        }
+        assert_eq!(counts[&Errors::NotFound], 5);
+        assert_eq!(counts[&Errors::Timeout], 2);
+        assert_eq!(counts[&Errors::PermissionDenied], 1);
+    }
+
+    #[test]
+    fn test_bounded_error_array_user_item_is_not_the_notice() {
+        let mut errors = ErrorArray::with_capacity_limit(2, OverflowPolicy::DropOldest);
+        // Looks like a notice, but is an ordinary error and must be treated as one
+        errors.push(ErrorArrayItem::new(Errors::GeneralError, "user").context("discarded", "7"));
+        errors.push(ErrorArrayItem::new(Errors::NotFound, "a"));
+        assert!(errors.overflow_notice().is_none());
+        errors.push(ErrorArrayItem::new(Errors::NotFound, "b"));
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors.to_string(),
+            "NotFound: a\nNotFound: b\nGeneralError: 1 errors discarded, limit is 2 [discarded=1]"
+        );
+
+        // Popping the notice resets the count, the next pop is a real error
+        assert_eq!(errors.pop().err_mesg, "1 errors discarded, limit is 2");
+        assert_eq!(errors.discarded(), 0);
+        assert_eq!(errors.pop().err_mesg, "b");
+        assert_eq!(messages(&errors.to_vec()), vec!["a"]);
+    }
+
+    #[test]
+    fn test_bounded_error_array_retain_after_overflow() {
+        let mut errors = fill_bounded(OverflowPolicy::DropNewest, 5);
+        errors.retain_kind(Errors::Timeout);
+        assert_eq!(messages(&errors.to_vec()), vec!["e1"]);
+        assert_eq!(errors.discarded(), 0);
+
+        // Keeping only the notice leaves a non-empty array that still fails
+        let mut errors = fill_bounded(OverflowPolicy::DropNewest, 5);
+        errors.retain(|item| item.err_type == Errors::GeneralError);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors.discarded(), 2);
+        assert_eq!(errors.to_string_vec().len(), 1);
+
+        errors.retain(|_| false);
+        assert!(errors.is_empty());
+        assert_eq!(errors.discarded(), 0);
+        assert_eq!(errors.to_string(), "");
+        assert_eq!(errors.into_exit_code(), std::process::ExitCode::SUCCESS);
+    }
 }
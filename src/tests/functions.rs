@@ -10,10 +10,13 @@ mod tests {
     use nix::unistd::{Gid, Uid};
 
     use crate::{
-        errors::{UnifiedResult as uf, WarningArray},
+        errors::{Errors, UnifiedResult as uf, WarningArray, Warnings},
         functions::{
-            create_hash, del_dir, del_file, generate_random_string, is_string_in_file, make_dir,
-            make_file, path_present, set_file_ownership, set_file_permission, tar, truncate, untar,
+            create_hash, default_sync_policy, del_dir, del_file, fmt_count, fmt_rate,
+            generate_random_string, group_digits, is_string_in_file, make_dir, make_file,
+            path_present, set_default_sync_policy, set_digit_separator, set_file_ownership,
+            set_file_permission, tar, truncate, untar, write_and_swap, write_and_swap_with_policy,
+            SyncPolicy, SYNC_CALLS,
         },
         types::PathType,
     };
@@ -54,7 +57,7 @@ mod tests {
     #[test]
     fn trimming() {
         let result = truncate("Hello, World", 5);
-        assert_eq!(result, "Hello".into());
+        assert_eq!(result, "Hello");
     }
 
     #[test]
@@ -68,7 +71,7 @@ mod tests {
         let result = create_hash("hash");
         assert_eq!(
             result,
-            "d04b98f48e8f8bcc15c6ae5ac050801cd6dcfd428fb5f9e65c4e16e7807340fa".into()
+            "d04b98f48e8f8bcc15c6ae5ac050801cd6dcfd428fb5f9e65c4e16e7807340fa"
         );
     }
 
@@ -203,6 +206,18 @@ mod tests {
         assert!(tar_file.exists());
     }
 
+    #[test]
+    fn test_create_tar_missing_folder() {
+        let output_dir = PathType::temp_dir().unwrap();
+        let missing = PathType::PathBuf(output_dir.to_path().join("missing"));
+        let tar_path = PathType::PathBuf(output_dir.to_path().join("missing.tar.gz"));
+
+        // The kind from the io error is kept, the message says what was being archived
+        let err = tar(&missing, &tar_path).get_err().unwrap();
+        assert_eq!(err.err_type, Errors::NotFound);
+        assert!(err.err_mesg.starts_with("archiving "));
+    }
+
     #[test]
     fn test_untar() {
         // Create a temporary directory for input files and output extraction
@@ -261,4 +276,194 @@ mod tests {
         // Try extracting the invalid tar file
         assert!(untar(&invalid_tar_path, &output_path).is_err());
     }
+
+    // Tests that read or change the global sync policy hold this lock
+    static SYNC_POLICY_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    // Clears the global sync policy override when dropped, even if the test panics
+    struct RestoreSyncPolicy;
+
+    impl Drop for RestoreSyncPolicy {
+        fn drop(&mut self) {
+            set_default_sync_policy(None);
+        }
+    }
+
+    #[test]
+    fn test_write_and_swap_preserves_open_handle() {
+        use std::io::Read;
+
+        let _lock = SYNC_POLICY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let dir = PathType::temp_dir().unwrap();
+        let target = dir.join("asset.txt");
+        fs::write(&target, b"old content").unwrap();
+
+        // Hold the old inode open across the swap
+        let mut old_handle = File::open(&target).unwrap();
+
+        let backup = write_and_swap(&target, b"new content", true)
+            .uf_unwrap()
+            .unwrap()
+            .expect("a backup should have been made");
+
+        let mut old_data = String::new();
+        old_handle.read_to_string(&mut old_data).unwrap();
+        assert_eq!(old_data, "old content");
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new content");
+        assert_eq!(
+            backup.to_path_buf(),
+            dir.join("asset.txt.prev").to_path_buf()
+        );
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "old content");
+
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_write_and_swap_without_existing_file() {
+        let _lock = SYNC_POLICY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = PathType::temp_dir().unwrap();
+        let target = dir.join("fresh.txt");
+
+        let backup = write_and_swap(&target, b"first", true).uf_unwrap().unwrap();
+        assert!(backup.is_none());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "first");
+
+        // Without keep_backup the previous version is simply replaced
+        let backup = write_and_swap(&target, b"second", false)
+            .uf_unwrap()
+            .unwrap();
+        assert!(backup.is_none());
+        assert!(!dir.join("fresh.txt.prev").exists());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "second");
+    }
+
+    #[test]
+    fn test_write_and_swap_replaces_backup() {
+        let _lock = SYNC_POLICY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = PathType::temp_dir().unwrap();
+        let target = dir.join("config.toml");
+        fs::write(&target, b"v1").unwrap();
+
+        write_and_swap(&target, b"v2", true).uf_unwrap().unwrap();
+        let backup = write_and_swap(&target, b"v3", true)
+            .uf_unwrap()
+            .unwrap()
+            .expect("a backup should have been made");
+
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "v2");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "v3");
+        // The staged backup link is renamed away, not left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    }
+
+    fn sync_calls() -> usize {
+        SYNC_CALLS.with(|calls| calls.get())
+    }
+
+    #[test]
+    fn test_sync_policy_plumbing() {
+        let dir = PathType::temp_dir().unwrap();
+        let target = dir.join("synced.txt");
+
+        let start = sync_calls();
+        write_and_swap_with_policy(&target, b"always", false, SyncPolicy::Always)
+            .uf_unwrap()
+            .unwrap();
+        // File data and the parent directory
+        assert_eq!(sync_calls() - start, 2);
+
+        let start = sync_calls();
+        write_and_swap_with_policy(&target, b"on close", false, SyncPolicy::OnClose)
+            .uf_unwrap()
+            .unwrap();
+        assert_eq!(sync_calls() - start, 1);
+
+        let start = sync_calls();
+        write_and_swap_with_policy(&target, b"never", false, SyncPolicy::Never)
+            .uf_unwrap()
+            .unwrap();
+        assert_eq!(sync_calls(), start);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "never");
+    }
+
+    #[test]
+    fn test_sync_policy_global_override() {
+        let _lock = SYNC_POLICY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(default_sync_policy(SyncPolicy::Always), SyncPolicy::Always);
+
+        let restore = RestoreSyncPolicy;
+        set_default_sync_policy(Some(SyncPolicy::Never));
+        assert_eq!(default_sync_policy(SyncPolicy::Always), SyncPolicy::Never);
+
+        let dir = PathType::temp_dir().unwrap();
+        let target = dir.join("override.txt");
+        let start = sync_calls();
+        write_and_swap(&target, b"fast", false).uf_unwrap().unwrap();
+        assert_eq!(sync_calls() - start, 0);
+
+        drop(restore);
+        assert_eq!(
+            default_sync_policy(SyncPolicy::OnClose),
+            SyncPolicy::OnClose
+        );
+    }
+
+    // Tests that read or change the global digit separator hold this lock
+    static SEPARATOR_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn test_group_digits_boundaries() {
+        let _guard = SEPARATOR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(group_digits(0u8), "0");
+        assert_eq!(group_digits(999u16), "999");
+        assert_eq!(group_digits(1000u16), "1,000");
+        assert_eq!(group_digits(1234567u32), "1,234,567");
+        assert_eq!(group_digits(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn test_fmt_count() {
+        let _guard = SEPARATOR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(fmt_count(0u8, "file", "files"), "0 files");
+        assert_eq!(fmt_count(1u8, "file", "files"), "1 file");
+        assert_eq!(fmt_count(1234u32, "file", "files"), "1,234 files");
+    }
+
+    #[test]
+    fn test_fmt_rate() {
+        use std::time::Duration;
+
+        assert_eq!(fmt_rate(500u32, Duration::from_secs(1)), "500.0/s");
+        assert_eq!(fmt_rate(12300u32, Duration::from_secs(1)), "12.3k/s");
+        assert_eq!(fmt_rate(3_000_000u32, Duration::from_secs(2)), "1.5M/s");
+        assert_eq!(fmt_rate(10u8, Duration::ZERO), "0/s");
+    }
+
+    #[test]
+    fn test_digit_separator_setter() {
+        let _guard = SEPARATOR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Restore the default before asserting so a failure can't leak into other tests
+        set_digit_separator('.');
+        let dotted = group_digits(1000000u32);
+        set_digit_separator(',');
+        assert_eq!(dotted, "1.000.000");
+    }
+
+    #[test]
+    fn test_delete_warnings_reach_caller() {
+        let missing = PathType::Str("/tmp/dusa_never_created_for_warnings".into());
+
+        for result in [del_file(&missing), del_dir(&missing)] {
+            let (_, warnings) = result.uf_unwrap_with_warnings().unwrap().strip_with();
+            assert_eq!(warnings.len(), 1);
+            let warning = warnings.into_vec().remove(0);
+            assert_eq!(warning.warn_type, Warnings::Warning);
+            assert_eq!(warning.warn_mesg.as_deref(), Some("The file didn't exist"));
+        }
+    }
 }
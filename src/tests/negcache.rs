@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::errors::{ErrorArrayItem, Errors};
+    use crate::negcache::NegativeCache;
+    use crate::stringy::Stringy;
+
+    fn failure() -> ErrorArrayItem {
+        ErrorArrayItem::new(Errors::ConnectionError, "connection refused")
+    }
+
+    #[test]
+    fn test_suppressed_within_ttl() {
+        let cache = NegativeCache::new(Duration::from_secs(60));
+        let key = Stringy::from("https://example.invalid");
+
+        assert!(cache.should_attempt(&key));
+        cache.record_failure(key.clone(), &failure());
+        cache.record_failure(key.clone(), &failure());
+        assert!(!cache.should_attempt(&key));
+
+        let snapshot = cache.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].key, key);
+        assert_eq!(snapshot[0].failures, 2);
+        assert_eq!(snapshot[0].last_error, failure());
+        assert!(snapshot[0].remaining_secs <= 60);
+
+        cache.record_success(&key);
+        assert!(cache.should_attempt(&key));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_expires_after_ttl() {
+        let cache = NegativeCache::new(Duration::from_millis(20));
+        let key = Stringy::from("/mnt/broken");
+
+        cache.record_failure(key.clone(), &failure());
+        assert!(!cache.should_attempt(&key));
+
+        thread::sleep(Duration::from_millis(40));
+        assert!(cache.snapshot().is_empty());
+        assert!(cache.should_attempt(&key));
+    }
+
+    #[test]
+    fn test_capacity_evicts_least_recent() {
+        let cache = NegativeCache::with_capacity(Duration::from_secs(60), 2);
+
+        cache.record_failure("a", &failure());
+        thread::sleep(Duration::from_millis(2));
+        cache.record_failure("b", &failure());
+        thread::sleep(Duration::from_millis(2));
+        cache.record_failure("a", &failure());
+        thread::sleep(Duration::from_millis(2));
+        cache.record_failure("c", &failure());
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.should_attempt(&Stringy::from("a")));
+        assert!(cache.should_attempt(&Stringy::from("b")));
+        assert!(!cache.should_attempt(&Stringy::from("c")));
+    }
+
+    #[test]
+    fn test_lookup_counts_as_use() {
+        let cache = NegativeCache::with_capacity(Duration::from_secs(60), 2);
+
+        cache.record_failure("a", &failure());
+        thread::sleep(Duration::from_millis(2));
+        cache.record_failure("b", &failure());
+        thread::sleep(Duration::from_millis(2));
+        assert!(!cache.should_attempt(&Stringy::from("a")));
+        thread::sleep(Duration::from_millis(2));
+        cache.record_failure("c", &failure());
+
+        assert!(!cache.should_attempt(&Stringy::from("a")));
+        assert!(cache.should_attempt(&Stringy::from("b")));
+    }
+
+    #[test]
+    fn test_recovers_from_poisoned_lock() {
+        let cache = std::sync::Arc::new(NegativeCache::new(Duration::from_secs(60)));
+        cache.record_failure("a", &failure());
+
+        let poisoner = std::sync::Arc::clone(&cache);
+        let _ = thread::spawn(move || poisoner.poison()).join();
+
+        assert!(!cache.should_attempt(&Stringy::from("a")));
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, os::unix::fs::symlink};
+
+    use tempfile::tempdir;
+
+    use crate::errors::Errors;
+    use crate::functions::guarded_del_file;
+    use crate::pathguard::PathGuard;
+    use crate::stringy::Stringy;
+    use crate::types::PathType;
+
+    struct Sandbox {
+        _dir: tempfile::TempDir,
+        root: PathType,
+        outside: PathType,
+    }
+
+    fn sandbox() -> Sandbox {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("root");
+        let outside = dir.path().join("outside");
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret"), "hunter2").unwrap();
+        fs::write(root.join("data").join("report.txt"), "ok").unwrap();
+        symlink(&outside, root.join("escape")).unwrap();
+
+        Sandbox {
+            _dir: dir,
+            root: PathType::PathBuf(root),
+            outside: PathType::PathBuf(outside),
+        }
+    }
+
+    fn guard(sandbox: &Sandbox) -> PathGuard {
+        PathGuard::new(vec![sandbox.root.clone()], vec![Stringy::from("*.key")])
+    }
+
+    #[test]
+    fn test_allows_paths_under_root() {
+        let sandbox = sandbox();
+        let guard = guard(&sandbox);
+
+        let existing = sandbox.root.join("data/report.txt");
+        assert_eq!(guard.check(&existing).unwrap(), existing);
+
+        let missing = sandbox.root.join("data/new/../later.txt");
+        assert_eq!(
+            guard.check(&missing).unwrap(),
+            sandbox.root.join("data/later.txt")
+        );
+    }
+
+    #[test]
+    fn test_rejects_symlink_escape() {
+        let sandbox = sandbox();
+        let guard = guard(&sandbox);
+
+        let escape = sandbox.root.join("escape/secret");
+        let err = guard.check(&escape).unwrap_err();
+        assert_eq!(err.err_type, Errors::PermissionDenied);
+        assert!(err.err_mesg.contains("outside the allowed roots"));
+    }
+
+    #[test]
+    fn test_rejects_parent_traversal() {
+        let sandbox = sandbox();
+        let guard = guard(&sandbox);
+
+        let traversal = sandbox.root.join("data/../../outside/secret");
+        let err = guard.check(&traversal).unwrap_err();
+        assert_eq!(err.err_type, Errors::PermissionDenied);
+    }
+
+    #[test]
+    fn test_rejects_denied_pattern() {
+        let sandbox = sandbox();
+        let guard = guard(&sandbox);
+
+        let key = sandbox.root.join("data/server.key");
+        let err = guard.check(&key).unwrap_err();
+        assert_eq!(err.err_type, Errors::PermissionDenied);
+        assert!(err.err_mesg.contains("'*.key'"));
+    }
+
+    #[test]
+    fn test_denied_pattern_ignores_root_components() {
+        let sandbox = sandbox();
+        let root = sandbox.root.join(".app");
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(root.join("config/app.toml"), "ok").unwrap();
+        let guard = PathGuard::new(vec![root.clone()], vec![Stringy::from(".*")]);
+
+        let config = root.join("config/app.toml");
+        assert_eq!(guard.check(&config).unwrap(), config);
+        assert_eq!(guard.check(&root).unwrap(), root);
+
+        let hidden = root.join("config/.secret");
+        let err = guard.check(&hidden).unwrap_err();
+        assert_eq!(err.err_type, Errors::PermissionDenied);
+        assert!(err.err_mesg.contains("'.*'"));
+    }
+
+    #[test]
+    fn test_guarded_del_file() {
+        let sandbox = sandbox();
+        let guard = guard(&sandbox);
+
+        let escape = sandbox.root.join("escape/secret");
+        assert!(guarded_del_file(&guard, &escape).is_err());
+        assert!(sandbox.outside.join("secret").exists());
+
+        let report = sandbox.root.join("data/report.txt");
+        assert!(guarded_del_file(&guard, &report).is_ok());
+        assert!(!report.exists());
+    }
+
+    #[test]
+    fn test_guarded_del_file_removes_link_not_target() {
+        let sandbox = sandbox();
+        let guard = guard(&sandbox);
+        let target = sandbox.root.join("data/report.txt");
+        let link = sandbox.root.join("data/latest");
+        symlink(&target, &link).unwrap();
+
+        assert!(guarded_del_file(&guard, &link).is_ok());
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert!(target.exists());
+
+        // The link is checked by where it sits, not where it points
+        let escape = sandbox.root.join("escape");
+        assert!(guarded_del_file(&guard, &escape).is_ok());
+        assert!(sandbox.outside.join("secret").exists());
+    }
+}
@@ -99,4 +99,282 @@ mod tests {
         let path = PathType::temp_dir().unwrap();
         assert!(path.exists())
     }
+
+    fn invalid_utf8_path(dir: &Path) -> PathBuf {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+        dir.join(OsStr::from_bytes(b"inv\xffalid"))
+    }
+
+    #[test]
+    fn test_utf8_checks() {
+        let valid = PathType::from("/some/path");
+        assert!(valid.is_valid_utf8());
+        assert_eq!(valid.to_stringy_lossy(), "/some/path");
+
+        let invalid = PathType::PathBuf(invalid_utf8_path(Path::new("/tmp")));
+        assert!(!invalid.is_valid_utf8());
+        assert_eq!(invalid.to_stringy_lossy(), "/tmp/inv\u{FFFD}alid");
+        assert_eq!(format!("{}", invalid), "/tmp/inv\u{FFFD}alid");
+    }
+
+    #[test]
+    fn test_serialize_invalid_utf8_errors() {
+        let invalid = PathType::PathBuf(invalid_utf8_path(Path::new("/tmp")));
+        let err = serde_json::to_string(&invalid).unwrap_err();
+        assert!(err.to_string().contains("InvalidUtf8Data"));
+
+        let boxed = PathType::Path(invalid_utf8_path(Path::new("/tmp")).into_boxed_path());
+        assert!(serde_json::to_string(&boxed).is_err());
+
+        // Valid paths keep their existing representation
+        let valid = PathType::PathBuf(PathBuf::from("/some/path"));
+        let json = serde_json::to_string(&valid).unwrap();
+        assert_eq!(json, r#"{"PathBuf":"/some/path"}"#);
+        assert_eq!(serde_json::from_str::<PathType>(&json).unwrap(), valid);
+    }
+
+    #[test]
+    fn test_delete_invalid_utf8_path() {
+        let dir = PathType::temp_dir().unwrap();
+        let invalid = PathType::PathBuf(invalid_utf8_path(&dir));
+        std::fs::write(&invalid, b"data").unwrap();
+        assert!(invalid.exists());
+
+        invalid.delete().unwrap();
+        assert!(!invalid.exists());
+
+        // Deleting again only logs that the path is gone
+        assert!(invalid.delete().is_ok());
+    }
+
+    #[test]
+    fn test_join_relative() {
+        let variants = [
+            PathType::PathBuf(PathBuf::from("/srv/app")),
+            PathType::Path(Box::from(Path::new("/srv/app"))),
+            PathType::Str("/srv/app".into()),
+            PathType::Content(String::from("/srv/app")),
+        ];
+        for base in variants {
+            let joined = base.join("config/app.toml");
+            assert_eq!(
+                joined,
+                PathType::PathBuf(PathBuf::from("/srv/app/config/app.toml"))
+            );
+        }
+
+        let relative = PathType::Str("data".into()).join(Path::new("cache"));
+        assert_eq!(relative.to_path_buf(), PathBuf::from("data/cache"));
+    }
+
+    #[test]
+    #[allow(clippy::join_absolute_paths)] // replacing the base is the behaviour under test
+    fn test_join_absolute_replaces_base() {
+        let base = PathType::Str("/srv/app".into());
+        let joined = base.join("/etc/passwd");
+        assert_eq!(joined.to_path_buf(), PathBuf::from("/etc/passwd"));
+        assert_eq!(
+            joined.to_path_buf(),
+            Path::new("/srv/app").join("/etc/passwd")
+        );
+    }
+
+    #[test]
+    fn test_component_accessors() {
+        let path = PathType::Str("/srv/app/archive.tar.gz".into());
+        assert_eq!(path.extension().unwrap(), "gz");
+        assert_eq!(path.file_name().unwrap(), "archive.tar.gz");
+        assert_eq!(
+            path.parent(),
+            Some(PathType::PathBuf(PathBuf::from("/srv/app")))
+        );
+
+        let dotfile = PathType::Content(String::from("/home/user/.bashrc"));
+        assert!(dotfile.extension().is_none());
+        assert_eq!(dotfile.file_name().unwrap(), ".bashrc");
+    }
+
+    #[test]
+    fn test_component_accessors_on_root_and_bare_names() {
+        let root = PathType::Str("/".into());
+        assert!(root.extension().is_none());
+        assert!(root.file_name().is_none());
+        assert!(root.parent().is_none());
+
+        let bare = PathType::Str("app.toml".into());
+        assert_eq!(bare.file_name().unwrap(), "app.toml");
+        assert!(bare.parent().is_none());
+
+        let up = PathType::Str("/srv/..".into());
+        assert!(up.file_name().is_none());
+    }
+
+    #[test]
+    fn test_component_accessors_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let raw = PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9.t\xfft"));
+        let path = PathType::PathBuf(raw);
+        assert_eq!(path.file_name().unwrap(), "caf\u{FFFD}.t\u{FFFD}t");
+        assert_eq!(path.extension().unwrap(), "t\u{FFFD}t");
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let dir = PathType::temp_dir().unwrap();
+        std::fs::create_dir(dir.join("real")).unwrap();
+        std::os::unix::fs::symlink(dir.join("real"), dir.join("link")).unwrap();
+
+        let expected = dir.canonicalize().unwrap().join("real");
+        let via_link = PathType::PathBuf(dir.to_path_buf().join("link/../real/./"));
+        assert_eq!(dir.join("link").canonicalize().unwrap(), expected);
+        assert_eq!(via_link.canonicalize().unwrap(), expected);
+
+        let missing = dir.join("missing.txt");
+        let err = missing.canonicalize().unwrap_err();
+        assert_eq!(err.err_type, crate::errors::Errors::NotFound);
+
+        dir.delete().unwrap();
+    }
+
+    #[test]
+    fn test_try_canonicalize_keeps_missing_paths() {
+        let missing = PathType::Str("/definitely/missing/../file".into());
+        assert_eq!(missing.try_canonicalize().unwrap(), missing);
+
+        let root = PathType::Str("/".into());
+        assert_eq!(
+            root.try_canonicalize().unwrap(),
+            PathType::PathBuf(PathBuf::from("/"))
+        );
+    }
+
+    #[test]
+    fn test_temp_file() {
+        let file = PathType::temp_file().unwrap();
+        assert!(file.is_file());
+        assert!(file.starts_with(std::env::temp_dir()));
+
+        std::fs::write(&file, b"scratch").unwrap();
+        assert_eq!(std::fs::read(&file).unwrap(), b"scratch");
+
+        let other = PathType::temp_file().unwrap();
+        assert_ne!(file, other);
+
+        file.delete().unwrap();
+        other.delete().unwrap();
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_read_and_write_helpers() {
+        let file = PathType::temp_file().unwrap();
+        file.write_bytes(b"first version, longer").unwrap();
+        file.write_bytes("héllo\n".as_bytes()).unwrap();
+
+        assert_eq!(file.read_to_string().unwrap(), "héllo\n");
+        assert_eq!(file.read_to_bytes().unwrap(), "héllo\n".as_bytes());
+
+        file.write_bytes(&[0xff, 0xfe]).unwrap();
+        let err = file.read_to_string().unwrap_err();
+        assert_eq!(err.err_type, crate::errors::Errors::ReadingFile);
+        assert_eq!(file.read_to_bytes().unwrap(), vec![0xff, 0xfe]);
+
+        file.delete().unwrap();
+    }
+
+    #[test]
+    fn test_read_and_write_helpers_report_path() {
+        let missing = PathType::Str("/definitely/missing/file.txt".into());
+        let err = missing.read_to_bytes().unwrap_err();
+        assert_eq!(err.err_type, crate::errors::Errors::ReadingFile);
+        assert!(err.err_mesg.starts_with("/definitely/missing/file.txt: "));
+
+        let err = missing.write_bytes(b"data").unwrap_err();
+        assert_eq!(err.err_type, crate::errors::Errors::CreatingFile);
+    }
+
+    #[test]
+    fn test_is_absolute_and_relative() {
+        let absolute = PathType::Str("/etc/app.conf".into());
+        assert!(absolute.is_absolute());
+        assert!(!absolute.is_relative());
+
+        let relative = PathType::Content(String::from("config/app.conf"));
+        assert!(relative.is_relative());
+        assert!(!relative.is_absolute());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_hidden() {
+        assert!(PathType::Str("/home/user/.bashrc".into()).is_hidden());
+        assert!(PathType::Str(".git".into()).is_hidden());
+        assert!(PathType::Str("/home/user/.config/".into()).is_hidden());
+        assert!(!PathType::Str("/home/.user/notes.txt".into()).is_hidden());
+        assert!(!PathType::Str("/".into()).is_hidden());
+        assert!(!PathType::Str("/srv/..".into()).is_hidden());
+    }
+
+    #[test]
+    fn test_size_and_age() {
+        let file = PathType::temp_file().unwrap();
+        assert_eq!(file.size().unwrap(), 0);
+        file.write_bytes(&[0u8; 1234]).unwrap();
+        assert_eq!(file.size().unwrap(), 1234);
+
+        let age = file.age().unwrap();
+        assert!(age < std::time::Duration::from_secs(60), "{:?}", age);
+
+        let dir = PathType::temp_dir().unwrap();
+        assert_eq!(dir.size().unwrap(), std::fs::metadata(&dir).unwrap().len());
+
+        let missing = PathType::Str("/definitely/missing".into());
+        assert_eq!(
+            missing.size().unwrap_err().err_type,
+            crate::errors::Errors::NotFound
+        );
+        assert!(missing.age().is_err());
+
+        file.delete().unwrap();
+        dir.delete().unwrap();
+    }
+
+    fn leftover_temp_files(dir: &PathType) -> Vec<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect()
+    }
+
+    #[test]
+    fn test_atomic_write() {
+        let dir = PathType::temp_dir().unwrap();
+        let config = dir.join("app.toml");
+
+        config.atomic_write(b"port = 80\n").unwrap();
+        assert_eq!(config.read_to_string().unwrap(), "port = 80\n");
+        config.atomic_write(b"port = 8080\n").unwrap();
+        assert_eq!(config.read_to_string().unwrap(), "port = 8080\n");
+
+        assert!(leftover_temp_files(&dir).is_empty());
+        assert!(!dir.join("app.toml.prev").exists());
+        dir.delete().unwrap();
+    }
+
+    #[test]
+    fn test_atomic_write_cleans_up_on_rename_failure() {
+        let dir = PathType::temp_dir().unwrap();
+        let target = dir.join("occupied");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("keep.txt"), b"x").unwrap();
+
+        // Renaming a file over a non-empty directory fails
+        assert!(target.atomic_write(b"data").is_err());
+        assert!(leftover_temp_files(&dir).is_empty());
+        assert!(target.is_dir());
+        dir.delete().unwrap();
+    }
 }
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use tokio::time::Duration;
+
+    use crate::registry::{self, LockState, RegistryEntry, RegistryKind};
+    use crate::rwarc::LockWithTimeout;
+
+    fn find(name: &str) -> Option<RegistryEntry> {
+        registry::snapshot()
+            .into_iter()
+            .find(|entry| entry.name.as_str() == name)
+    }
+
+    #[tokio::test]
+    async fn test_named_lock_is_registered() {
+        let lock = LockWithTimeout::new_named("registry_test_idle", 5u32);
+
+        let entry = find("registry_test_idle").expect("lock should be registered");
+        assert_eq!(entry.kind, RegistryKind::LockWithTimeout);
+        assert_eq!(entry.state, LockState::Unlocked);
+        assert!(entry.age_secs < 5);
+
+        drop(lock);
+        assert!(find("registry_test_idle").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unnamed_lock_is_not_registered() {
+        let _lock = LockWithTimeout::new(String::from("registry_test_unnamed"));
+        assert!(registry::snapshot()
+            .iter()
+            .all(|entry| entry.name.as_str() != "registry_test_unnamed"));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_reports_contention() {
+        let lock = LockWithTimeout::new_named("registry_test_contended", Vec::<u8>::new());
+        let reader = LockWithTimeout::new_named("registry_test_reader", 0u8);
+
+        let write_guard = lock.try_write_tracked(None).await.unwrap();
+        let read_guard = reader.try_read_tracked(None).await.unwrap();
+
+        // A second writer is reported as pending while it waits, then times out
+        let contender = lock.clone();
+        let waiting = tokio::spawn(async move {
+            contender
+                .try_write_with_timeout(Some(Duration::from_millis(300)))
+                .await
+                .is_err()
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(find("registry_test_contended").unwrap().pending_writers, 1);
+        assert!(waiting.await.unwrap());
+
+        let entry = find("registry_test_contended").unwrap();
+        assert_eq!(entry.state, LockState::WriteLocked);
+        assert_eq!(entry.pending_writers, 0);
+        assert_eq!(entry.stats.writes, 1);
+        assert_eq!(entry.stats.timeouts, 1);
+
+        let entry = find("registry_test_reader").unwrap();
+        assert_eq!(entry.state, LockState::ReadLocked);
+        assert_eq!(entry.stats.reads, 1);
+
+        drop(write_guard);
+        drop(read_guard);
+        assert_eq!(
+            find("registry_test_contended").unwrap().state,
+            LockState::Unlocked
+        );
+
+        let json = registry::snapshot_json().unwrap();
+        assert!(json.contains("\"registry_test_contended\""));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_prunes_dropped() {
+        drop(LockWithTimeout::new_named("registry_test_pruned", 1u8));
+        let _lock = LockWithTimeout::new_named("registry_test_pruner", 2u8);
+
+        registry::snapshot();
+        let names = registry::recorded_names();
+        assert!(names
+            .iter()
+            .all(|name| name.as_str() != "registry_test_pruned"));
+        assert!(names
+            .iter()
+            .any(|name| name.as_str() == "registry_test_pruner"));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_does_not_touch_lock() {
+        let lock = LockWithTimeout::new_named("registry_test_untouched", 0u8);
+        let read_guard = lock.try_read_tracked(None).await.unwrap();
+
+        assert_eq!(
+            find("registry_test_untouched").unwrap().state,
+            LockState::ReadLocked
+        );
+
+        // Snapshots alone never count as acquisitions or cause timeouts
+        for _ in 0..10 {
+            registry::snapshot();
+        }
+        drop(read_guard);
+
+        let entry = find("registry_test_untouched").unwrap();
+        assert_eq!(entry.state, LockState::Unlocked);
+        assert_eq!(entry.stats.reads, 1);
+        assert_eq!(entry.stats.timeouts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_plain_guards_and_unnamed_locks() {
+        let lock = LockWithTimeout::new_named("registry_test_plain", 0u8);
+        let guard: tokio::sync::RwLockWriteGuard<'_, u8> = lock.try_write().await.unwrap();
+
+        // Plain guards are counted but do not show up in the state
+        let entry = find("registry_test_plain").unwrap();
+        assert_eq!(entry.stats.writes, 1);
+        assert_eq!(entry.state, LockState::Unlocked);
+        drop(guard);
+
+        let unnamed = LockWithTimeout::new(0u8);
+        drop(unnamed.try_read_tracked(None).await.unwrap());
+        assert!(unnamed.stats().is_none());
+    }
+
+    #[test]
+    fn test_concurrent_registration() {
+        let locks: Vec<LockWithTimeout<usize>> = (0..8)
+            .map(|worker| {
+                std::thread::spawn(move || {
+                    (0..25)
+                        .map(|i| {
+                            LockWithTimeout::new_named(
+                                format!("registry_test_many_{}_{}", worker, i),
+                                i,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+
+        let registered = registry::snapshot()
+            .into_iter()
+            .filter(|entry| entry.name.as_str().starts_with("registry_test_many_"))
+            .count();
+        assert_eq!(registered, locks.len());
+    }
+
+    #[test]
+    fn test_registration_without_snapshot_is_bounded() {
+        // Never calls snapshot, registration alone has to keep the records in check
+        for i in 0..(registry::PENDING_LIMIT * 20) {
+            drop(LockWithTimeout::new_named(
+                format!("registry_test_churn_{}", i),
+                i,
+            ));
+        }
+
+        assert!(registry::pending_len() < registry::PENDING_LIMIT * 2);
+        let kept = registry::recorded_names()
+            .iter()
+            .filter(|name| name.as_str().starts_with("registry_test_churn_"))
+            .count();
+        assert!(kept < registry::PENDING_LIMIT * 2);
+    }
+}
@@ -57,4 +57,42 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_with_read_releases_lock() {
+        let mut state: HashMap<AppName, Status> = HashMap::new();
+        state.insert(AppName("web".to_string()), Status);
+        let lock_with_timeout = Arc::new(LockWithTimeout::new(state));
+
+        let len = lock_with_timeout.with_read(|state| state.len()).await;
+        assert_eq!(len.unwrap(), 1);
+
+        let result = lock_with_timeout
+            .try_write_with_timeout(Some(Duration::from_millis(50)))
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_with_read_async() {
+        let mut state: HashMap<AppName, Status> = HashMap::new();
+        state.insert(AppName("web".to_string()), Status);
+        let lock_with_timeout = Arc::new(LockWithTimeout::new(state));
+
+        let found = lock_with_timeout
+            .with_read_async(|state| {
+                let found = state.contains_key(&AppName("web".to_string()));
+                async move {
+                    tokio::task::yield_now().await;
+                    found
+                }
+            })
+            .await;
+        assert!(found.unwrap());
+
+        let guard = lock_with_timeout.try_write().await.unwrap();
+        let result = lock_with_timeout.with_read(|state| state.len()).await;
+        assert!(result.is_err());
+        drop(guard);
+    }
 }
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, process::ExitCode};
+
+    use tempfile::tempdir;
+
+    use crate::errors::Errors;
+    use crate::selfcheck::SelfCheck;
+    use crate::types::PathType;
+
+    #[test]
+    fn test_selfcheck_reports_deliberate_failure() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("app.conf");
+        fs::write(&config, "port=8080").unwrap();
+        let missing_log = dir.path().join("log").join("app.log");
+
+        let result = SelfCheck::new()
+            .require_dir_writable(PathType::PathBuf(dir.path().to_path_buf()))
+            .require_file_readable(PathType::PathBuf(config))
+            .require_file_readable(PathType::PathBuf(missing_log))
+            .require_min_disk_free(PathType::PathBuf(dir.path().to_path_buf()), 1)
+            .require_user("root")
+            .require_command(PathType::Str("/bin/sh".into()))
+            .run();
+
+        let (report, _warnings) = result.into_result_with_warnings().unwrap().into_parts();
+        assert_eq!(report.outcomes.len(), 6);
+        assert!(!report.passed());
+
+        let failed: Vec<_> = report.outcomes.iter().filter(|o| !o.passed).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].check.as_str(), "file readable");
+        assert!(failed[0].target.ends_with("log/app.log"));
+
+        assert_eq!(report.failures.len(), 1);
+        assert!(report.failures.contains_type(Errors::NotFound));
+
+        let table = report.to_string();
+        assert_eq!(table.lines().count(), 6);
+        assert_eq!(
+            table
+                .lines()
+                .filter(|line| line.starts_with("FAIL"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_selfcheck_missing_user_and_disk() {
+        let dir = tempdir().unwrap();
+        let report = SelfCheck::new()
+            .require_user("no-such-user-for-selfcheck")
+            .require_min_disk_free(PathType::PathBuf(dir.path().to_path_buf()), u64::MAX)
+            .run()
+            .unwrap();
+
+        assert!(!report.passed());
+        assert!(report.failures.contains_type(Errors::IdentityInvalid));
+        assert!(report.failures.contains_type(Errors::GeneralError));
+        assert_eq!(report.into_exit_code(), ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn test_empty_selfcheck_passes() {
+        let report = SelfCheck::new().run().unwrap();
+        assert!(report.passed());
+        assert_eq!(report.to_string(), "");
+        assert_eq!(report.into_exit_code(), ExitCode::SUCCESS);
+    }
+}
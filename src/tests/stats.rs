@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs};
+
+    use crate::errors::stats::{load_history, trend, RunStats, Trend};
+    use crate::errors::{ErrorArray, ErrorArrayItem, Errors};
+    use crate::types::PathType;
+
+    fn run(timeouts: usize, not_found: usize) -> RunStats {
+        RunStats::from_counts(HashMap::from([
+            (Errors::Timeout, timeouts),
+            (Errors::NotFound, not_found),
+        ]))
+    }
+
+    #[test]
+    fn test_append_and_trend() {
+        let dir = PathType::temp_dir().unwrap();
+        let history = dir.join("errors.jsonl");
+
+        for (timeouts, not_found) in [(1, 5), (3, 4), (6, 0)] {
+            run(timeouts, not_found).append_to(&history).unwrap();
+        }
+
+        let runs = load_history(&history, 10).uf_unwrap().unwrap();
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[2].count(Errors::Timeout), 6);
+        assert_eq!(runs[2].count(Errors::PermissionDenied), 0);
+        // Zero counts are left out of the record
+        assert!(!runs[2].counts.contains_key(&Errors::NotFound));
+
+        assert_eq!(trend(&runs, Errors::Timeout), Trend::Increasing);
+        assert_eq!(trend(&runs, Errors::NotFound), Trend::Decreasing);
+        assert_eq!(trend(&runs, Errors::PermissionDenied), Trend::Steady);
+        assert_eq!(trend(&runs[..1], Errors::Timeout), Trend::Steady);
+
+        let recent = load_history(&history, 2).uf_unwrap().unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].count(Errors::Timeout), 3);
+    }
+
+    #[test]
+    fn test_load_history_skips_corrupt_lines() {
+        let dir = PathType::temp_dir().unwrap();
+        let history = dir.join("errors.jsonl");
+        run(1, 0).append_to(&history).unwrap();
+        let mut content = fs::read_to_string(&history).unwrap();
+        content.push_str("{\"timestamp\": truncated\n\n");
+        fs::write(&history, content).unwrap();
+        run(2, 0).append_to(&history).unwrap();
+
+        let loaded = load_history(&history, 10)
+            .into_result_with_warnings()
+            .unwrap();
+        let (runs, warnings) = (loaded.data, loaded.warning);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings.to_string_vec()[0].contains("line 2"));
+
+        let missing = dir.join("missing.jsonl");
+        assert!(load_history(&missing, 10).uf_unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_counts_array() {
+        let errors = ErrorArray::new(vec![
+            ErrorArrayItem::new(Errors::Timeout, "a"),
+            ErrorArrayItem::new(Errors::Timeout, "b"),
+            ErrorArrayItem::new(Errors::NotFound, "c"),
+        ]);
+        let stats = RunStats::snapshot(&errors);
+        assert_eq!(stats.count(Errors::Timeout), 2);
+        assert_eq!(stats.count(Errors::NotFound), 1);
+        assert_eq!(stats.pid, std::process::id());
+        // Snapshotting leaves the errors in place
+        assert_eq!(errors.len(), 3);
+    }
+}
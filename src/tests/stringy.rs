@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use crate::errors::Errors;
     use crate::stringy::Stringy;
     use std::sync::Arc;
 
@@ -123,4 +124,314 @@ mod tests {
 
         assert_eq!(stringy.as_str(), original)
     }
+
+    fn both_variants(text: &str) -> [Stringy; 2] {
+        let immutable = Stringy::from(text);
+        let mut mutable = Stringy::from("");
+        mutable.mutate(|s| s.push_str(text));
+        assert!(matches!(mutable, Stringy::Mutable(_)));
+        [immutable, mutable]
+    }
+
+    #[test]
+    fn test_len_and_is_empty() {
+        for s in both_variants("héllo") {
+            assert_eq!(s.len(), 6);
+            assert!(!s.is_empty());
+        }
+        for s in both_variants("") {
+            assert_eq!(s.len(), 0);
+            assert!(s.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let prefix = String::from("/etc");
+        let other = Stringy::from("/etc/app");
+        for s in both_variants("/etc/app.conf") {
+            assert!(s.starts_with("/etc/"));
+            assert!(s.starts_with(&prefix));
+            assert!(s.starts_with(&other));
+            assert!(s.starts_with('/'));
+            assert!(s.starts_with(['/', '.']));
+            assert!(s.starts_with(&['x', '/'][..]));
+            assert!(s.starts_with(""));
+            assert!(!s.starts_with("etc"));
+            assert!(!s.starts_with('e'));
+            assert!(s.starts_with(|c: char| c.is_ascii_punctuation()));
+            assert!(!s.starts_with(char::is_alphabetic));
+        }
+    }
+
+    #[test]
+    fn test_ends_with() {
+        let suffixes: [&str; 2] = [".conf", "app.conf"];
+        for s in both_variants("/etc/app.conf") {
+            assert!(s.ends_with(".conf"));
+            assert!(suffixes.iter().all(|suffix| s.ends_with(suffix)));
+            assert!(s.ends_with('f'));
+            assert!(s.ends_with(['f', 'g']));
+            assert!(!s.ends_with(".toml"));
+            assert!(!s.ends_with('/'));
+            assert!(s.ends_with(char::is_lowercase));
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        for s in both_variants("grüße aus Köln") {
+            assert!(s.contains("aus"));
+            assert!(s.contains('ü'));
+            assert!(s.contains(['x', 'K']));
+            assert!(s.contains(&String::from("ße")));
+            assert!(!s.contains("Berlin"));
+            assert!(!s.contains('z'));
+            assert!(s.contains(char::is_uppercase));
+            assert!(!s.contains(char::is_numeric));
+        }
+        for s in both_variants("") {
+            assert!(s.contains(""));
+            assert!(!s.contains('a'));
+        }
+    }
+
+    #[test]
+    fn test_case_conversion_ascii() {
+        for s in both_variants("Hello, World 42") {
+            let upper = s.to_uppercase();
+            assert!(matches!(upper, Stringy::Immutable(_)));
+            assert_eq!(upper.as_str(), "HELLO, WORLD 42");
+            assert_eq!(s.to_lowercase().as_str(), "hello, world 42");
+        }
+    }
+
+    #[test]
+    fn test_case_conversion_unicode() {
+        for s in both_variants("Straße ΟΔΥΣΣΕΎΣ") {
+            // ß expands to SS and a final sigma lowercases to ς
+            assert_eq!(s.to_uppercase().as_str(), "STRASSE ΟΔΥΣΣΕΎΣ");
+            assert_eq!(s.to_lowercase().as_str(), "straße οδυσσεύς");
+        }
+
+        let mutable = both_variants("İstanbul")[1].clone();
+        assert_eq!(mutable.to_lowercase().as_str(), "i\u{307}stanbul");
+        assert!(matches!(mutable, Stringy::Mutable(_)));
+    }
+
+    #[test]
+    fn test_trim() {
+        for s in both_variants("  \t padded \n ") {
+            assert_eq!(s.trim().as_str(), "padded");
+            assert_eq!(s.trim_start().as_str(), "padded \n ");
+            assert_eq!(s.trim_end().as_str(), "  \t padded");
+            assert!(matches!(s.trim(), Stringy::Immutable(_)));
+        }
+    }
+
+    #[test]
+    fn test_trim_only_whitespace() {
+        for s in both_variants(" \t\r\n ") {
+            for trimmed in [s.trim(), s.trim_start(), s.trim_end()] {
+                assert_eq!(trimmed, Stringy::Immutable(Arc::from("")));
+            }
+        }
+    }
+
+    #[test]
+    fn test_trim_unicode_whitespace() {
+        // no-break space, em space and ideographic space are all Unicode whitespace
+        for s in both_variants("\u{a0}\u{2003}wörd\u{3000}") {
+            assert_eq!(s.trim().as_str(), "wörd");
+            assert_eq!(s.trim_start().as_str(), "wörd\u{3000}");
+            assert_eq!(s.trim_end().as_str(), "\u{a0}\u{2003}wörd");
+        }
+    }
+
+    #[test]
+    fn test_trim_without_whitespace_shares_arc() {
+        let s = Stringy::from("compact");
+        let trimmed = s.trim();
+        match (&s, &trimmed) {
+            (Stringy::Immutable(original), Stringy::Immutable(result)) => {
+                assert!(Arc::ptr_eq(original, result));
+            }
+            _ => panic!("Expected Immutable variants."),
+        }
+
+        let mutable = both_variants("compact")[1].clone();
+        assert_eq!(mutable.trim_end().as_str(), "compact");
+    }
+
+    #[test]
+    fn test_replace() {
+        for s in both_variants("a-b-c-d") {
+            assert_eq!(s.replace("-", "+"), Stringy::from("a+b+c+d"));
+            assert_eq!(s.replacen("-", "+", 2), Stringy::from("a+b+c-d"));
+            assert_eq!(s.replacen("-", "+", 0), s);
+            assert!(matches!(s.replace("-", ""), Stringy::Immutable(_)));
+        }
+    }
+
+    #[test]
+    fn test_replace_without_match_shares_arc() {
+        let s = Stringy::from("unchanged");
+        for replaced in [s.replace("x", "y"), s.replacen("x", "y", 3)] {
+            match (&s, &replaced) {
+                (Stringy::Immutable(original), Stringy::Immutable(result)) => {
+                    assert!(Arc::ptr_eq(original, result));
+                }
+                _ => panic!("Expected Immutable variants."),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        for s in both_variants("42") {
+            assert_eq!(s.parse::<u32>(), Ok(42));
+            assert_eq!(s.parse_or(7u8), 42);
+            assert!(s.parse::<bool>().is_err());
+            assert!(!s.parse_or(false));
+        }
+        assert_eq!(Stringy::from("true").parse::<bool>(), Ok(true));
+    }
+
+    #[test]
+    fn test_parse_into_error() {
+        let s = Stringy::from("eighty");
+        let err = s
+            .parse_into_error::<u16>(Errors::ConfigParsing)
+            .unwrap_err();
+        assert_eq!(err.err_type, Errors::ConfigParsing);
+        assert_eq!(
+            err.err_mesg,
+            Stringy::from("Failed to parse 'eighty' as u16: invalid digit found in string")
+        );
+
+        let port = Stringy::from("80").parse_into_error::<u16>(Errors::ConfigParsing);
+        assert_eq!(port.unwrap(), 80);
+    }
+
+    #[test]
+    fn test_from_utf8() {
+        let s = Stringy::from_utf8("héllo".as_bytes().to_vec()).unwrap();
+        assert!(matches!(s, Stringy::Immutable(_)));
+        assert_eq!(s.as_str(), "héllo");
+
+        let err = Stringy::from_utf8(vec![b'a', 0xff, b'b']).unwrap_err();
+        assert_eq!(err.utf8_error().valid_up_to(), 1);
+        assert_eq!(err.into_bytes(), vec![b'a', 0xff, b'b']);
+    }
+
+    #[test]
+    fn test_from_utf8_lossy() {
+        let s = Stringy::from_utf8_lossy(&[b'a', 0xff, b'b']);
+        assert!(matches!(s, Stringy::Immutable(_)));
+        assert_eq!(s.as_str(), "a\u{FFFD}b");
+        assert_eq!(Stringy::from_utf8_lossy(b"plain").as_str(), "plain");
+    }
+
+    #[test]
+    fn test_eq_with_str_types() {
+        let owned = String::from("value");
+        for s in both_variants("value") {
+            assert_eq!(s, "value");
+            assert_eq!("value", s);
+            assert_eq!(s, *"value");
+            assert_eq!(*"value", s);
+            assert_eq!(s, owned);
+            assert_eq!(owned, s);
+
+            assert_ne!(s, "other");
+            assert_ne!("other", s);
+            assert_ne!(s, String::from("Value"));
+            assert_ne!(String::from("Value"), s);
+        }
+    }
+
+    #[test]
+    fn test_lines_match_str_lines() {
+        for text in [
+            "",
+            "one",
+            "one\n",
+            "one\ntwo",
+            "one\r\ntwo\n",
+            "\n\nthree\n\n",
+            "\r\n",
+        ] {
+            for s in both_variants(text) {
+                let lines: Vec<Stringy> = s.lines().collect();
+                let expected: Vec<&str> = text.lines().collect();
+                assert_eq!(lines.len(), expected.len(), "{:?}", text);
+                for (line, expected) in lines.iter().zip(expected) {
+                    assert!(matches!(line, Stringy::Immutable(_)));
+                    assert_eq!(line, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_words() {
+        for s in both_variants("  GET\t/index.html \r\n HTTP/1.1  ") {
+            let words: Vec<Stringy> = s.words().collect();
+            assert_eq!(words, ["GET", "/index.html", "HTTP/1.1"].map(Stringy::from));
+        }
+        assert_eq!(Stringy::from(" \t\n").words().count(), 0);
+        // Non-ASCII whitespace is not a separator
+        assert_eq!(Stringy::from("a\u{3000}b c").words().count(), 2);
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut s = Stringy::with_capacity(64);
+        assert!(s.is_empty());
+        assert!(s.capacity() >= 64);
+
+        let before = s.capacity();
+        s.mutate(|text| text.push_str("grown in place"));
+        assert!(matches!(s, Stringy::Mutable(_)));
+        assert_eq!(s.capacity(), before);
+
+        s.shrink_to_fit();
+        assert_eq!(s.capacity(), s.len());
+        assert_eq!(s, "grown in place");
+    }
+
+    #[test]
+    fn test_capacity_of_immutable() {
+        let mut s = Stringy::from("fixed");
+        assert_eq!(s.capacity(), 5);
+        s.shrink_to_fit();
+        assert!(matches!(s, Stringy::Immutable(_)));
+        assert_eq!(s.capacity(), 5);
+    }
+
+    #[test]
+    fn test_add() {
+        for left in both_variants("foo") {
+            for right in both_variants("bar") {
+                let joined = left.clone() + right.clone();
+                assert!(matches!(joined, Stringy::Immutable(_)));
+                assert_eq!(joined, "foobar");
+            }
+            let joined = left.clone() + "baz";
+            assert!(matches!(joined, Stringy::Immutable(_)));
+            assert_eq!(joined, "foobaz");
+        }
+    }
+
+    #[test]
+    fn test_add_assign() {
+        for mut left in both_variants("foo") {
+            for right in both_variants("bar") {
+                left += right;
+            }
+            left += "!";
+            assert!(matches!(left, Stringy::Mutable(_)));
+            assert_eq!(left, "foobarbar!");
+        }
+    }
 }
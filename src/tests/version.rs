@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::version::{SoftwareVersion, Version, VersionCode};
+    use crate::errors::Errors;
+    use crate::version::{SoftwareVersion, Version, VersionCode, VersionPart};
 
     #[test]
     fn test_version_creation() {
@@ -36,6 +37,30 @@ mod tests {
         assert!(Version::compare_versions(&version1, &version3));
     }
 
+    #[test]
+    fn test_version_comparison_production_patch() {
+        let current = Version::new("1.2.3", VersionCode::Production);
+        let next_patch = Version::new("1.2.4", VersionCode::Production);
+        assert!(!Version::compare_versions(&current, &next_patch));
+        assert!(!Version::compare_versions(&next_patch, &current));
+
+        let candidate = Version::new("1.2.4", VersionCode::ReleaseCandidate);
+        assert!(!Version::compare_versions(&current, &candidate));
+        let candidate = Version::new("1.2.3", VersionCode::ReleaseCandidate);
+        assert!(Version::compare_versions(&current, &candidate));
+    }
+
+    #[test]
+    fn test_version_comparison_unparsable_number() {
+        let current = Version::new("1.2.3", VersionCode::Production);
+        let garbled = Version::new("1.two.3", VersionCode::Production);
+        assert!(!Version::compare_versions(&current, &garbled));
+
+        let garbled = Version::new("one", VersionCode::Beta);
+        let candidate = Version::new("1.0.0", VersionCode::ReleaseCandidate);
+        assert!(!Version::compare_versions(&candidate, &garbled));
+    }
+
     #[test]
     fn test_version_from_string() {
         let version_str = "1.2.3b";
@@ -137,4 +162,120 @@ mod tests {
         assert_eq!(decoded.code, VersionCode::Patched); // Default fallback
         assert_eq!(decoded.number.as_str(), "0.0.0"); // Default values for major, minor, patch
     }
+
+    #[test]
+    fn test_increment_parts() {
+        let version = Version::new("3.7.9", VersionCode::ReleaseCandidate);
+
+        let major = version.increment_major().unwrap();
+        assert_eq!(major.number.as_str(), "4.0.0");
+        assert_eq!(major.code, VersionCode::ReleaseCandidate);
+        assert_eq!(version.increment_minor().unwrap().number.as_str(), "3.8.0");
+        assert_eq!(version.increment_patch().unwrap().number.as_str(), "3.7.10");
+
+        assert_eq!(version.bump(VersionPart::Major), version.increment_major());
+        assert_eq!(version.bump(VersionPart::Minor), version.increment_minor());
+        assert_eq!(version.bump(VersionPart::Patch), version.increment_patch());
+    }
+
+    #[test]
+    fn test_increment_encoding_limits() {
+        let top = Version::new("31.15.15", VersionCode::Production);
+        assert!(top.increment_major().is_none());
+        assert!(top.increment_minor().is_none());
+        assert!(top.increment_patch().is_none());
+
+        // Resetting the lower parts keeps a major bump in range
+        let bumped = Version::new("30.15.15", VersionCode::Production)
+            .increment_major()
+            .unwrap();
+        assert_eq!(Version::decode(bumped.encode()), bumped);
+
+        let invalid = Version::new("not.a.version", VersionCode::Beta);
+        assert!(invalid.bump(VersionPart::Patch).is_none());
+    }
+
+    #[test]
+    fn test_version_code_from_str() {
+        let codes = [
+            ("P", VersionCode::Production),
+            ("RC", VersionCode::ReleaseCandidate),
+            ("b", VersionCode::Beta),
+            ("a", VersionCode::Alpha),
+            ("*", VersionCode::Patched),
+        ];
+        for (text, code) in codes {
+            assert_eq!(text.parse::<VersionCode>().unwrap(), code);
+        }
+
+        let err = "rc".parse::<VersionCode>().unwrap_err();
+        assert_eq!(err.err_type, Errors::InvalidType);
+        assert!(err.err_mesg.contains("'rc'"), "{}", err.err_mesg);
+    }
+
+    #[test]
+    fn test_version_from_str() {
+        let version: Version = "2.1.0RC".parse().unwrap();
+        assert_eq!(
+            version,
+            Version::new("2.1.0", VersionCode::ReleaseCandidate)
+        );
+        assert_eq!(
+            "0.4.2*".parse::<Version>().unwrap().code,
+            VersionCode::Patched
+        );
+
+        for invalid in ["1.2.3", "1.2.3x", ""] {
+            let err = invalid.parse::<Version>().unwrap_err();
+            assert_eq!(err.err_type, Errors::InvalidType);
+        }
+    }
+
+    #[test]
+    fn test_software_version_from_env() {
+        // Cargo sets CARGO_PKG_VERSION for the test binary at runtime as well
+        let version = SoftwareVersion::new_from_env(VersionCode::Beta);
+        assert_eq!(
+            version.application.number.as_str(),
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(version.library.number.as_str(), crate::VERSION);
+        assert_eq!(version.application.code, VersionCode::Beta);
+
+        let or_dummy = SoftwareVersion::new_from_env_or_dummy(VersionCode::Beta);
+        assert_eq!(or_dummy, version);
+    }
+
+    #[test]
+    fn test_channel_predicates() {
+        let predicates = |code: VersionCode| {
+            let version = Version::new("1.0.0", code);
+            (
+                version.is_stable(),
+                version.is_prerelease(),
+                version.is_hotfix(),
+            )
+        };
+
+        assert_eq!(predicates(VersionCode::Production), (true, false, false));
+        assert_eq!(
+            predicates(VersionCode::ReleaseCandidate),
+            (false, true, false)
+        );
+        assert_eq!(predicates(VersionCode::Beta), (false, true, false));
+        assert_eq!(predicates(VersionCode::Alpha), (false, true, false));
+        assert_eq!(predicates(VersionCode::Patched), (false, false, true));
+    }
+
+    #[test]
+    fn test_software_version_header_string() {
+        let version = SoftwareVersion {
+            application: Version::new("1.2.3", VersionCode::Production),
+            library: Version::new("3.1.2", VersionCode::ReleaseCandidate),
+        };
+
+        let header = version.as_header_string();
+        assert_eq!(header, "app/1.2.3P lib/3.1.2RC");
+        assert!(header.chars().all(|c| c.is_ascii_graphic() || c == ' '));
+    }
 }
@@ -1,14 +1,16 @@
 use std::{
-    fmt, fs,
+    fmt, fs, io,
     ops::Deref,
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 
 use serde::{Deserialize, Serialize};
-use tempfile::{tempdir /*tempfile*/};
+use tempfile::{tempdir, NamedTempFile};
 
 use crate::{
     errors::{ErrorArrayItem, Errors},
+    functions::write_and_swap,
     log,
     log::LogLevel,
     stringy::Stringy,
@@ -22,11 +24,16 @@ use crate::{
 /// - `Path`: Represents a borrowed path.
 /// - `str`: Represents a borrowed string path.
 /// - `Content`: Represents a path as a string content.
+///
+/// Paths are serialized as strings. Serializing a `PathBuf` or `Path` that is not valid
+/// UTF-8 fails with an `InvalidUtf8Data` error instead of silently replacing bytes.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PathType {
     /// Represents an owned path buffer.
+    #[serde(serialize_with = "serialize_utf8_path")]
     PathBuf(PathBuf),
     /// Represents a borrowed path.
+    #[serde(serialize_with = "serialize_utf8_path")]
     Path(Box<Path>),
     /// Represents a borrowed string path.
     Str(Box<str>),
@@ -36,6 +43,22 @@ pub enum PathType {
     Stringy(Stringy),
 }
 
+/// Serializes a path as a string, refusing paths that are not valid UTF-8.
+fn serialize_utf8_path<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+where
+    P: AsRef<Path>,
+    S: serde::Serializer,
+{
+    let path: &Path = path.as_ref();
+    match path.to_str() {
+        Some(data) => serializer.serialize_str(data),
+        None => Err(serde::ser::Error::custom(ErrorArrayItem::new(
+            Errors::InvalidUtf8Data,
+            format!("{} is not valid UTF-8", path.to_string_lossy()),
+        ))),
+    }
+}
+
 /// A trait for types that can be converted into a `PathBuf`.
 pub trait CopyPath {
     /// Returns a `PathBuf` representing the path.
@@ -85,6 +108,88 @@ impl PathType {
         self.copy_path().as_path().into()
     }
 
+    /// Joins `other` onto the path like `Path::join`, returning a `PathType::PathBuf`.
+    /// An absolute `other` replaces the path entirely.
+    ///
+    /// ```rust
+    /// use std::path::PathBuf;
+    /// use dusa_collection_utils::types::PathType;
+    ///
+    /// let base_dir = PathType::Str("/etc/app".into());
+    /// let config = base_dir.join("config/app.toml");
+    /// assert_eq!(config, PathType::PathBuf(PathBuf::from("/etc/app/config/app.toml")));
+    /// ```
+    pub fn join<P: AsRef<Path>>(&self, other: P) -> PathType {
+        PathType::PathBuf(self.to_path_buf().join(other))
+    }
+
+    /// Returns the extension of the final component without the leading dot.
+    ///
+    /// Like the other component accessors, non-UTF-8 bytes are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`. Call `Path::extension(&path)` instead when the
+    /// exact bytes matter.
+    pub fn extension(&self) -> Option<Stringy> {
+        Path::extension(self).map(|ext| Stringy::from(ext.to_string_lossy().into_owned()))
+    }
+
+    /// Returns the final component of the path, `None` for a root or a path ending in `..`.
+    /// Non-UTF-8 bytes are replaced, see [`PathType::extension`].
+    pub fn file_name(&self) -> Option<Stringy> {
+        Path::file_name(self).map(|name| Stringy::from(name.to_string_lossy().into_owned()))
+    }
+
+    /// Returns the path without its final component, `None` for a root or a bare
+    /// relative name such as `app.toml` that has no directory portion.
+    pub fn parent(&self) -> Option<PathType> {
+        match Path::parent(self) {
+            Some(dir) if !dir.as_os_str().is_empty() => Some(PathType::PathBuf(dir.to_path_buf())),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the path is absolute, same as `Path::is_absolute`.
+    pub fn is_absolute(&self) -> bool {
+        Path::is_absolute(self)
+    }
+
+    /// Returns `true` if the path is relative, same as `Path::is_relative`.
+    pub fn is_relative(&self) -> bool {
+        Path::is_relative(self)
+    }
+
+    /// Returns `true` if the final component names a hidden file.
+    ///
+    /// On Unix that is a name starting with `.`, the path does not need to exist.
+    /// On Windows the hidden attribute is read from the file's metadata.
+    #[cfg(unix)]
+    pub fn is_hidden(&self) -> bool {
+        use std::os::unix::ffi::OsStrExt;
+
+        Path::file_name(self).is_some_and(|name| name.as_bytes().starts_with(b"."))
+    }
+
+    /// Returns `true` if the file has the hidden attribute set. A path that can not be
+    /// read is not hidden.
+    #[cfg(windows)]
+    pub fn is_hidden(&self) -> bool {
+        use std::os::windows::fs::MetadataExt;
+
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        fs::symlink_metadata(self)
+            .is_ok_and(|meta| meta.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+    }
+
+    /// Checks if the path is valid UTF-8. String backed variants always are.
+    pub fn is_valid_utf8(&self) -> bool {
+        self.as_os_str().to_str().is_some()
+    }
+
+    /// Converts the path into a `Stringy`, replacing invalid UTF-8 sequences
+    /// with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_stringy_lossy(&self) -> Stringy {
+        Stringy::from(self.as_os_str().to_string_lossy().into_owned())
+    }
+
     /// Attempts to delete the file or directory
     pub fn delete(&self) -> Result<(), ErrorArrayItem> {
         match self.exists() {
@@ -104,6 +209,76 @@ impl PathType {
         }
     }
 
+    /// Returns the size in bytes reported by `fs::metadata`, following symlinks.
+    /// For a directory this is the size of the directory entry, not of its contents.
+    pub fn size(&self) -> Result<u64, ErrorArrayItem> {
+        fs::metadata(self)
+            .map(|meta| meta.len())
+            .map_err(ErrorArrayItem::from)
+    }
+
+    /// Returns how long ago the file was last modified. A modification time in the
+    /// future, for example after a clock change, counts as zero.
+    pub fn age(&self) -> Result<Duration, ErrorArrayItem> {
+        let modified: SystemTime = fs::metadata(self)
+            .and_then(|meta| meta.modified())
+            .map_err(ErrorArrayItem::from)?;
+        Ok(modified.elapsed().unwrap_or(Duration::ZERO))
+    }
+
+    /// Reads the whole file as UTF-8 text. Failures, including invalid UTF-8, are
+    /// reported as `ReadingFile` naming the path.
+    pub fn read_to_string(&self) -> Result<String, ErrorArrayItem> {
+        fs::read_to_string(self).map_err(|err| self.io_error(Errors::ReadingFile, err))
+    }
+
+    /// Reads the whole file as bytes. Failures are reported as `ReadingFile`.
+    pub fn read_to_bytes(&self) -> Result<Vec<u8>, ErrorArrayItem> {
+        fs::read(self).map_err(|err| self.io_error(Errors::ReadingFile, err))
+    }
+
+    /// Writes `content` to the file, creating it or truncating an existing one.
+    /// Failures are reported as `CreatingFile`.
+    pub fn write_bytes(&self, content: &[u8]) -> Result<(), ErrorArrayItem> {
+        fs::write(self, content).map_err(|err| self.io_error(Errors::CreatingFile, err))
+    }
+
+    /// Replaces the file's contents so readers see either the old or the new data, never
+    /// a partial write.
+    ///
+    /// `content` goes to a randomly named sibling temp file that is then renamed over the
+    /// path, which keeps the rename on one filesystem. The temp file is removed if any
+    /// step fails. This is [`write_and_swap`] without a backup.
+    pub fn atomic_write(&self, content: &[u8]) -> Result<(), ErrorArrayItem> {
+        write_and_swap(self, content, false)
+            .uf_unwrap()
+            .map(|_backup| ())
+    }
+
+    fn io_error(&self, kind: Errors, err: io::Error) -> ErrorArrayItem {
+        ErrorArrayItem::new(kind, format!("{}: {}", self, err))
+    }
+
+    /// Returns the absolute form of the path with every symlink followed and all `.`
+    /// and `..` components resolved, as a `PathType::PathBuf`.
+    ///
+    /// The path must exist, a missing path is reported as `NotFound`.
+    pub fn canonicalize(&self) -> Result<PathType, ErrorArrayItem> {
+        fs::canonicalize(self.to_path_buf())
+            .map(PathType::PathBuf)
+            .map_err(ErrorArrayItem::from)
+    }
+
+    /// Like [`PathType::canonicalize`], but returns the path unchanged if it does not exist.
+    /// Other failures, such as a permission error on a parent directory, are still errors.
+    pub fn try_canonicalize(&self) -> Result<PathType, ErrorArrayItem> {
+        match fs::canonicalize(self.to_path_buf()) {
+            Ok(path) => Ok(PathType::PathBuf(path)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(self.clone()),
+            Err(err) => Err(ErrorArrayItem::from(err)),
+        }
+    }
+
     pub fn temp_dir() -> Result<Self, ErrorArrayItem> {
         if let Ok(dir) = tempdir() {
             let path = dir.into_path();
@@ -116,23 +291,26 @@ impl PathType {
         }
     }
 
-    // pub fn temp_file() -> Result<Self, ErrorArrayItem> {
-    //     if let Ok(file) = tempfile() {
-    //         let file_meta = file.metadata().map_err(ErrorArrayItem::from)?;
-    //         let path = P
-
-    //         Ok(PathType::PathBuf(path))
-    //     } else {
-    //         Err(ErrorArrayItem::new(Errors::CreatingDirectory, "Failed to create a temp dir"))
-    //     }
-    // }
+    /// Creates an empty file in the system temp directory and returns its path.
+    ///
+    /// Like [`PathType::temp_dir`] the file is kept after this returns, it is not removed
+    /// when the path is dropped. Callers clean it up with [`PathType::delete`].
+    pub fn temp_file() -> Result<Self, ErrorArrayItem> {
+        match NamedTempFile::new().map(NamedTempFile::keep) {
+            Ok(Ok((_file, path))) => Ok(PathType::PathBuf(path)),
+            _ => Err(ErrorArrayItem::new(
+                Errors::CreatingFile,
+                "Failed to create a temp file",
+            )),
+        }
+    }
 }
 
 impl fmt::Display for PathType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            PathType::PathBuf(path_buf) => write!(f, "{}", path_buf.display()),
-            PathType::Path(path) => write!(f, "{}", path.display()),
+            PathType::PathBuf(path_buf) => write!(f, "{}", path_buf.to_string_lossy()),
+            PathType::Path(path) => write!(f, "{}", path.to_string_lossy()),
             PathType::Str(str_box) => write!(f, "{}", str_box),
             PathType::Content(content) => write!(f, "{}", content),
             PathType::Stringy(stringy) => write!(f, "{}", stringy),
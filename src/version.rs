@@ -1,8 +1,25 @@
+//! Application and library version numbers tagged with a release channel.
+//!
+//! Numbers are `MAJOR.MINOR.PATCH`. [`Version::compare_versions`] decides whether two
+//! versions can talk to each other using these rules, checked in order:
+//!
+//! * `Patched` on either side is always compatible, hot fixes bypass the check.
+//! * `Alpha` and `Beta` are compatible with each other regardless of number.
+//! * `ReleaseCandidate` is compatible with `ReleaseCandidate` or `Beta` of the same major.
+//! * `Production` is compatible with `Production` or `ReleaseCandidate` of the same major,
+//!   minor and patch.
+//! * Any other pairing, or a number that does not parse, is incompatible.
+
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{env, fmt, str::FromStr};
 
-use crate::stringy::Stringy;
+use crate::{
+    errors::{ErrorArrayItem, Errors},
+    log,
+    log::LogLevel,
+    stringy::Stringy,
+};
 
 /// Struct representing the version information of both application and library.
 #[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Clone)]
@@ -22,6 +39,38 @@ impl SoftwareVersion {
         }
     }
 
+    /// Pairs the running package's `CARGO_PKG_VERSION` with this library's [`crate::VERSION`].
+    ///
+    /// The variable is read at runtime, so it is set under `cargo run` and `cargo test` but
+    /// usually not for an installed binary. When it is missing the application version is
+    /// `0.0.0` and a warning is logged.
+    pub fn new_from_env(channel: VersionCode) -> Self {
+        let application_version: String = env::var("CARGO_PKG_VERSION").unwrap_or_else(|err| {
+            log!(
+                LogLevel::Warn,
+                "CARGO_PKG_VERSION unavailable ({}), using 0.0.0",
+                err
+            );
+            String::from("0.0.0")
+        });
+        Self::new(&application_version, crate::VERSION, channel)
+    }
+
+    /// Like [`SoftwareVersion::new_from_env`], but returns [`SoftwareVersion::dummy`] if
+    /// either version is missing or is not a plain `MAJOR.MINOR.PATCH` number.
+    pub fn new_from_env_or_dummy(channel: VersionCode) -> Self {
+        let version: SoftwareVersion = match env::var("CARGO_PKG_VERSION") {
+            Ok(application_version) => Self::new(&application_version, crate::VERSION, channel),
+            Err(_) => return Self::dummy(),
+        };
+
+        let parses = |version: &Version| Version::parse_version_parts(&version.number).is_some();
+        match parses(&version.application) && parses(&version.library) {
+            true => version,
+            false => Self::dummy(),
+        }
+    }
+
     /// Creates a `SoftwareVersion` instance with dummy version data.
     pub fn dummy() -> Self {
         let dummy_version = "0.0.0";
@@ -32,6 +81,18 @@ impl SoftwareVersion {
         }
     }
 
+    /// Formats both versions as `app/1.2.3P lib/3.1.2b`, without the terminal colors
+    /// `Display` adds, so the result is safe for a `User-Agent` or other header value.
+    pub fn as_header_string(&self) -> Stringy {
+        Stringy::from(format!(
+            "app/{}{} lib/{}{}",
+            self.application.number,
+            self.application.code.as_str(),
+            self.library.number,
+            self.library.code.as_str()
+        ))
+    }
+
     /// Compares the application and library versions with an incoming `SoftwareVersion`.
     //  This function is experimental and may change or be removed in the future.
     /// Use at your own risk.    
@@ -77,16 +138,76 @@ pub enum VersionCode {
     Patched, // If a quick patch is issued before the platform update, this code is used.
 }
 
-impl fmt::Display for VersionCode {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let code_str = match self {
+/// Component of a version number, used by [`Version::bump`].
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum VersionPart {
+    /// The first component, bumping it resets minor and patch.
+    Major,
+    /// The second component, bumping it resets patch.
+    Minor,
+    /// The third component.
+    Patch,
+}
+
+// Largest values the u16 encoding in `Version::encode` can hold
+const MAX_MAJOR: u32 = 0b11111;
+const MAX_MINOR: u32 = 0b1111;
+const MAX_PATCH: u32 = 0b1111;
+
+impl VersionCode {
+    /// Returns the suffix for this code without any terminal coloring.
+    pub fn as_str(&self) -> &'static str {
+        match self {
             VersionCode::Production => "P",
             VersionCode::ReleaseCandidate => "RC",
             VersionCode::Beta => "b",
             VersionCode::Alpha => "a",
             VersionCode::Patched => "*",
-        };
-        write!(f, "{}", code_str.bold().red())
+        }
+    }
+}
+
+impl fmt::Display for VersionCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str().bold().red())
+    }
+}
+
+// Parses the suffix written by `Display`, without the coloring
+impl FromStr for VersionCode {
+    type Err = ErrorArrayItem;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "P" => Ok(VersionCode::Production),
+            "RC" => Ok(VersionCode::ReleaseCandidate),
+            "b" => Ok(VersionCode::Beta),
+            "a" => Ok(VersionCode::Alpha),
+            "*" => Ok(VersionCode::Patched),
+            _ => Err(ErrorArrayItem::new(
+                Errors::InvalidType,
+                format!(
+                    "'{}' is not a version code, expected one of P, RC, b, a or *",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = ErrorArrayItem;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Version::from_string(s.to_owned()).ok_or_else(|| {
+            ErrorArrayItem::new(
+                Errors::InvalidType,
+                format!(
+                    "'{}' is not a version, expected a number followed by a code like 1.2.3P",
+                    s
+                ),
+            )
+        })
     }
 }
 
@@ -159,6 +280,74 @@ impl Version {
         }
     }
 
+    /// Returns `true` for `Production` releases.
+    pub fn is_stable(&self) -> bool {
+        self.code == VersionCode::Production
+    }
+
+    /// Returns `true` for `Alpha`, `Beta` and `ReleaseCandidate` builds that have not been
+    /// released to production yet.
+    pub fn is_prerelease(&self) -> bool {
+        matches!(
+            self.code,
+            VersionCode::Alpha | VersionCode::Beta | VersionCode::ReleaseCandidate
+        )
+    }
+
+    /// Returns `true` for `Patched` hot fixes, which bypass compatibility checks in
+    /// [`Version::compare_versions`].
+    pub fn is_hotfix(&self) -> bool {
+        self.code == VersionCode::Patched
+    }
+
+    /// Returns the next major version with the same code, for example `1.4.2` becomes `2.0.0`.
+    /// Returns `None` if the number does not parse or the result would not fit [`Version::encode`].
+    pub fn increment_major(&self) -> Option<Version> {
+        self.bump(VersionPart::Major)
+    }
+
+    /// Returns the next minor version with the same code, for example `1.4.2` becomes `1.5.0`.
+    /// Returns `None` if the number does not parse or the result would not fit [`Version::encode`].
+    pub fn increment_minor(&self) -> Option<Version> {
+        self.bump(VersionPart::Minor)
+    }
+
+    /// Returns the next patch version with the same code, for example `1.4.2` becomes `1.4.3`.
+    /// Returns `None` if the number does not parse or the result would not fit [`Version::encode`].
+    pub fn increment_patch(&self) -> Option<Version> {
+        self.bump(VersionPart::Patch)
+    }
+
+    /// Increments `part` and resets the components below it.
+    ///
+    /// ```rust
+    /// use dusa_collection_utils::version::{Version, VersionCode, VersionPart};
+    ///
+    /// let current = Version::new("1.4.2", VersionCode::Beta);
+    /// let next = current.bump(VersionPart::Minor).unwrap();
+    /// assert_eq!(next, Version::new("1.5.0", VersionCode::Beta));
+    ///
+    /// // 15 is the largest minor the encoding can hold
+    /// assert!(Version::new("1.15.0", VersionCode::Beta).bump(VersionPart::Minor).is_none());
+    /// ```
+    pub fn bump(&self, part: VersionPart) -> Option<Version> {
+        let (major, minor, patch) = Self::parse_version_parts(&self.number)?;
+        let (major, minor, patch) = match part {
+            VersionPart::Major => (major + 1, 0, 0),
+            VersionPart::Minor => (major, minor + 1, 0),
+            VersionPart::Patch => (major, minor, patch + 1),
+        };
+
+        if major > MAX_MAJOR || minor > MAX_MINOR || patch > MAX_PATCH {
+            return None;
+        }
+
+        Some(Version {
+            number: format!("{}.{}.{}", major, minor, patch).into(),
+            code: self.code.clone(),
+        })
+    }
+
     /// Returns the version as a `Stringy`.
     pub fn get_as_string(&self) -> Stringy {
         Stringy::from(&self.to_string())
@@ -180,18 +369,26 @@ impl Version {
             (VersionCode::ReleaseCandidate, VersionCode::ReleaseCandidate)
             | (VersionCode::ReleaseCandidate, VersionCode::Beta)
             | (VersionCode::Beta, VersionCode::ReleaseCandidate) => {
-                let (incoming_major, _, _) = Self::parse_version_parts(&incoming.number).unwrap();
-                let (current_major, _, _) = Self::parse_version_parts(&current.number).unwrap();
-                incoming_major == current_major
+                match (
+                    Self::parse_version_parts(&incoming.number),
+                    Self::parse_version_parts(&current.number),
+                ) {
+                    (Some((incoming_major, _, _)), Some((current_major, _, _))) => {
+                        incoming_major == current_major
+                    }
+                    _ => false,
+                }
             }
             (VersionCode::Production, VersionCode::ReleaseCandidate)
             | (VersionCode::ReleaseCandidate, VersionCode::Production)
             | (VersionCode::Production, VersionCode::Production) => {
-                let (incoming_major, incoming_minor, _) =
-                    Self::parse_version_parts(&incoming.number).unwrap();
-                let (current_major, current_minor, _) =
-                    Self::parse_version_parts(&current.number).unwrap();
-                incoming_major == current_major && incoming_minor == current_minor
+                match (
+                    Self::parse_version_parts(&incoming.number),
+                    Self::parse_version_parts(&current.number),
+                ) {
+                    (Some(incoming_parts), Some(current_parts)) => incoming_parts == current_parts,
+                    _ => false,
+                }
             }
             _ => false,
         }
@@ -210,14 +407,7 @@ impl Version {
         if let Some(pos) = pos {
             let number_part = &version_str[..pos];
             let code_part = &version_str[pos..];
-            let code = match code_part {
-                "P" => VersionCode::Production,
-                "RC" => VersionCode::ReleaseCandidate,
-                "b" => VersionCode::Beta,
-                "a" => VersionCode::Alpha,
-                "*" => VersionCode::Patched,
-                _ => return None,
-            };
+            let code: VersionCode = code_part.parse().ok()?;
             Some(Version {
                 number: Stringy::from(number_part),
                 code,
@@ -232,7 +422,7 @@ impl Version {
         Self::from_string(version_str.to_string())
     }
 
-    /// Parses a version string into major and minor components.
+    /// Parses a version string into major, minor and patch components.
     fn parse_version_parts(version: &str) -> Option<(u32, u32, u32)> {
         let parts: Vec<&str> = version.split('.').collect();
         if parts.len() != 3 {